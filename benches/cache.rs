@@ -0,0 +1,57 @@
+#![feature(test)]
+
+extern crate test;
+
+use semver::{CompiledReq, MatchCache, Version, VersionReq};
+use test::{black_box, Bencher};
+
+const REQ: &str = "1.2.3 - 1.4.0 || ^2.1 || ~3.4.5 || >=4.0.0-rc.1 <4.2 || 5.x || =6.0.1 \
+    || >6.5 <7 || ^8.0.0-beta.2 || <=9.1.1 >=9.0.3 || ~>10.4";
+
+// Resolver-like workload: many queries for a few hundred distinct versions.
+fn workload() -> Vec<Version> {
+    let mut versions = Vec::new();
+    for i in 0..10_000u64 {
+        let mut version = Version::new(i % 11, i % 7, i % 5);
+        if i % 3 == 0 {
+            version.pre = "rc.1".parse().unwrap();
+        }
+        versions.push(version);
+    }
+    versions
+}
+
+#[bench]
+fn matches_uncached(b: &mut Bencher) {
+    let req = VersionReq::parse(REQ).unwrap();
+    let versions = workload();
+    b.iter(|| {
+        for version in black_box(&versions) {
+            black_box(req.matches(version));
+        }
+    });
+}
+
+#[bench]
+fn matches_cached(b: &mut Bencher) {
+    let req = VersionReq::parse(REQ).unwrap();
+    let versions = workload();
+    b.iter(|| {
+        let mut cache = MatchCache::new(req.clone());
+        for version in black_box(&versions) {
+            black_box(cache.matches(version));
+        }
+    });
+}
+
+#[bench]
+fn matches_compiled(b: &mut Bencher) {
+    let req = VersionReq::parse(REQ).unwrap();
+    let versions = workload();
+    b.iter(|| {
+        let compiled = CompiledReq::new(req.clone());
+        for version in black_box(&versions) {
+            black_box(compiled.matches(version));
+        }
+    });
+}
@@ -22,3 +22,31 @@ fn parse_version_req(b: &mut Bencher) {
     let text = "^1.2.3";
     b.iter(|| black_box(text).parse::<VersionReq>().unwrap());
 }
+
+#[bench]
+fn parse_version_corpus(b: &mut Bencher) {
+    let corpus = [
+        "0.1.0",
+        "1.0.0",
+        "0.2.19",
+        "1.0.136",
+        "0.11.13",
+        "2.33.3",
+        "0.3.21",
+        "1.4.0",
+        "0.4.17",
+        "1.12.34",
+        "0.7.0-alpha.1",
+        "1.0.0-rc.1",
+        "3.0.0-beta.5",
+        "0.12.20+1.1.0",
+        "1.2.3-4+5",
+        "2021.11.20",
+        "18446744073709551615.0.0",
+    ];
+    b.iter(|| {
+        for text in black_box(&corpus) {
+            black_box(text.parse::<Version>().unwrap());
+        }
+    });
+}
@@ -0,0 +1,35 @@
+#![feature(test)]
+
+extern crate test;
+
+use semver::{Version, VersionPool};
+use test::{black_box, Bencher};
+
+// Resolver-like workload: many references to about a thousand distinct versions.
+// The 100,000 versions take 4,000,000 bytes as a Vec<Version>, and about
+// 490,000 as handles into a pool of the 1,122 distinct ones, counting each
+// pooled version twice for the pool's lookup table.
+fn workload() -> Vec<Version> {
+    let mut versions = Vec::new();
+    for i in 0..100_000u64 {
+        let mut version = Version::new(i % 3, i % 17, i % 11);
+        if i % 5 == 0 {
+            version.pre = "rc.1".parse().unwrap();
+        }
+        versions.push(version);
+    }
+    versions
+}
+
+#[bench]
+fn intern_versions(b: &mut Bencher) {
+    let versions = workload();
+
+    b.iter(|| {
+        let mut pool = VersionPool::new();
+        for version in black_box(&versions) {
+            black_box(pool.intern(version.clone()));
+        }
+        pool
+    });
+}
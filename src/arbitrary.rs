@@ -0,0 +1,151 @@
+// Every value generated here is one that the parser could have produced, so
+// that it survives a round trip through Display and FromStr unchanged.
+
+use crate::alloc::string::{String, ToString};
+use crate::alloc::vec::Vec;
+use crate::{eval, interval};
+use crate::{parse, BuildMetadata, Comparator, Op, Prerelease, Version, VersionRange, VersionReq};
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+const IDENTIFIER_CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-";
+const NON_DIGITS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-";
+
+// Operators other than `*` and `~>`, which are only ever displayed in a form
+// that parses back to a different comparator.
+const OPS: &[Op] = &[
+    Op::Exact,
+    Op::Greater,
+    Op::GreaterEq,
+    Op::Less,
+    Op::LessEq,
+    Op::Tilde,
+    Op::Caret,
+];
+
+impl<'a> Arbitrary<'a> for Version {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Version {
+            major: u.arbitrary()?,
+            minor: u.arbitrary()?,
+            patch: u.arbitrary()?,
+            pre: arbitrary_pre(u)?,
+            build: arbitrary_build(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for VersionReq {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = u.int_in_range(0..=3)?;
+        let mut ranges = Vec::with_capacity(len);
+        for _ in 0..len {
+            ranges.push(arbitrary_range(u)?);
+        }
+        // An empty list and `>=0` on its own both parse as `*`.
+        Ok(parse::version_req_from_parsed(ranges))
+    }
+}
+
+fn arbitrary_range(u: &mut Unstructured) -> Result<VersionRange> {
+    match u.int_in_range(0..=2)? {
+        0 => Ok(VersionRange::Simple(arbitrary_comparator(u)?)),
+        1 => {
+            let mut left = arbitrary_partial(u, Op::Exact)?;
+            let mut right = arbitrary_partial(u, Op::Exact)?;
+            let range = VersionRange::Hyphen(left.clone(), right.clone());
+            let (lower, upper) = eval::range_bounds(&range);
+            if interval::is_empty(&lower, &upper) {
+                // The other way around is never empty, since each endpoint
+                // matches at least its own lowest version.
+                core::mem::swap(&mut left, &mut right);
+            }
+            Ok(VersionRange::Hyphen(left, right))
+        }
+        _ => {
+            let len = u.int_in_range(2..=3)?;
+            let mut comparators = Vec::with_capacity(len);
+            for _ in 0..len {
+                comparators.push(arbitrary_comparator(u)?);
+            }
+            Ok(VersionRange::Intersection(comparators))
+        }
+    }
+}
+
+fn arbitrary_comparator(u: &mut Unstructured) -> Result<Comparator> {
+    let op = *u.choose(OPS)?;
+    arbitrary_partial(u, op)
+}
+
+fn arbitrary_partial(u: &mut Unstructured, op: Op) -> Result<Comparator> {
+    let major = u.arbitrary()?;
+    let minor = if u.arbitrary()? {
+        Some(u.arbitrary()?)
+    } else {
+        None
+    };
+    let patch = if minor.is_some() && u.arbitrary()? {
+        Some(u.arbitrary()?)
+    } else {
+        None
+    };
+    // Only a complete version can have a pre-release.
+    let pre = if patch.is_some() {
+        arbitrary_pre(u)?
+    } else {
+        Prerelease::EMPTY
+    };
+    Ok(Comparator {
+        op,
+        major,
+        minor,
+        patch,
+        pre,
+    })
+}
+
+fn arbitrary_pre(u: &mut Unstructured) -> Result<Prerelease> {
+    let text = arbitrary_identifiers(u, |u| {
+        if u.arbitrary()? {
+            // Numeric, which must not have leading zeros.
+            Ok(u.arbitrary::<u64>()?.to_string())
+        } else {
+            // Alphanumeric, which must have a non-digit to not be numeric.
+            let mut identifier = arbitrary_identifier(u)?;
+            if identifier.bytes().all(|b| b.is_ascii_digit()) {
+                identifier.push(*u.choose(NON_DIGITS)? as char);
+            }
+            Ok(identifier)
+        }
+    })?;
+    Prerelease::new(&text).map_err(|_| Error::IncorrectFormat)
+}
+
+fn arbitrary_build(u: &mut Unstructured) -> Result<BuildMetadata> {
+    let text = arbitrary_identifiers(u, arbitrary_identifier)?;
+    BuildMetadata::new(&text).map_err(|_| Error::IncorrectFormat)
+}
+
+// Up to 3 dot-separated identifiers, possibly none.
+fn arbitrary_identifiers(
+    u: &mut Unstructured,
+    identifier: fn(&mut Unstructured) -> Result<String>,
+) -> Result<String> {
+    let mut text = String::new();
+    for i in 0..u.int_in_range(0..=3)? {
+        if i > 0 {
+            text.push('.');
+        }
+        text.push_str(&identifier(u)?);
+    }
+    Ok(text)
+}
+
+fn arbitrary_identifier(u: &mut Unstructured) -> Result<String> {
+    let len = u.int_in_range(1..=8)?;
+    let mut identifier = String::with_capacity(len);
+    for _ in 0..len {
+        identifier.push(*u.choose(IDENTIFIER_CHARS)? as char);
+    }
+    Ok(identifier)
+}
@@ -19,5 +19,7 @@ pub(crate) use crate::alloc::vec::Vec;
 #[cfg(no_alloc_crate)] // rustc <1.36
 pub(crate) mod alloc {
     pub use std::alloc;
+    pub use std::collections;
+    pub use std::string;
     pub use std::vec;
 }
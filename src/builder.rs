@@ -0,0 +1,100 @@
+use crate::{BuildMetadata, Error, Prerelease, Version};
+
+/// Fallible, step-by-step construction of a [`Version`].
+///
+/// The pre-release and build metadata are validated as soon as they are set,
+/// but any error is held back until [`build_version`][Self::build_version],
+/// so that the setters can be chained.
+///
+/// # Example
+///
+/// ```
+/// use semver::{Version, VersionBuilder};
+///
+/// let version = VersionBuilder::new()
+///     .major(1)
+///     .minor(2)
+///     .patch(3)
+///     .pre("rc.1")
+///     .build("exp.5")
+///     .build_version()
+///     .unwrap();
+///
+/// assert_eq!(version, Version::parse("1.2.3-rc.1+exp.5").unwrap());
+/// ```
+#[derive(Default, Debug)]
+pub struct VersionBuilder {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Prerelease,
+    build: BuildMetadata,
+    failed: Option<Error>,
+}
+
+impl VersionBuilder {
+    /// Start from `0.0.0`, with an empty pre-release and build metadata.
+    pub fn new() -> Self {
+        VersionBuilder::default()
+    }
+
+    pub fn major(mut self, major: u64) -> Self {
+        self.major = major;
+        self
+    }
+
+    pub fn minor(mut self, minor: u64) -> Self {
+        self.minor = minor;
+        self
+    }
+
+    pub fn patch(mut self, patch: u64) -> Self {
+        self.patch = patch;
+        self
+    }
+
+    /// Set the pre-release, in the syntax accepted by [`Prerelease::new`].
+    pub fn pre(mut self, text: &str) -> Self {
+        match Prerelease::new(text) {
+            Ok(pre) => self.pre = pre,
+            Err(err) => self.fail(err),
+        }
+        self
+    }
+
+    /// Set the build metadata, in the syntax accepted by
+    /// [`BuildMetadata::new`].
+    pub fn build(mut self, text: &str) -> Self {
+        match BuildMetadata::new(text) {
+            Ok(build) => self.build = build,
+            Err(err) => self.fail(err),
+        }
+        self
+    }
+
+    /// Finish building the version.
+    ///
+    /// # Errors
+    ///
+    /// The first error from [`pre`][Self::pre] or [`build`][Self::build], if
+    /// either was given text that is not a valid pre-release or build
+    /// metadata.
+    pub fn build_version(self) -> Result<Version, Error> {
+        if let Some(err) = self.failed {
+            return Err(err);
+        }
+        Ok(Version {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            pre: self.pre,
+            build: self.build,
+        })
+    }
+
+    fn fail(&mut self, err: Error) {
+        if self.failed.is_none() {
+            self.failed = Some(err);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{Version, VersionReq};
+use core::hash::{BuildHasherDefault, Hasher};
+use std::collections::HashMap;
+
+/// Memoized [`VersionReq::matches`], for evaluating one requirement against
+/// the same versions over and over, as in the inner loop of a resolver.
+///
+/// Results are keyed by the whole version, meaning precedence and build
+/// metadata, and are always the same as calling `matches` on the requirement.
+/// Looking a version up costs about as much as hashing it, so caching pays off
+/// for requirements with several `||` alternatives, not for a single `^1.2.3`.
+///
+/// # Example
+///
+/// ```
+/// use semver::{MatchCache, Version, VersionReq};
+///
+/// let req = VersionReq::parse("^1.2.3 || ~2.1 || >=3.0.0-rc.1 <3.1").unwrap();
+/// let mut cache = MatchCache::new(req);
+///
+/// let version = Version::parse("2.1.7").unwrap();
+/// assert!(cache.matches(&version));
+/// assert!(cache.matches(&version));
+/// assert_eq!(cache.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MatchCache {
+    req: VersionReq,
+    results: HashMap<Version, bool, BuildHasherDefault<FxHasher>>,
+}
+
+impl MatchCache {
+    pub fn new(req: VersionReq) -> Self {
+        MatchCache {
+            req,
+            results: HashMap::default(),
+        }
+    }
+
+    pub fn req(&self) -> &VersionReq {
+        &self.req
+    }
+
+    /// The same as `self.req().matches(version)`, computed only the first time
+    /// each distinct version is seen.
+    pub fn matches(&mut self, version: &Version) -> bool {
+        if let Some(&matches) = self.results.get(version) {
+            return matches;
+        }
+        let matches = self.req.matches(version);
+        self.results.insert(version.clone(), matches);
+        matches
+    }
+
+    /// Number of distinct versions whose result is cached.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Forget all cached results.
+    pub fn clear(&mut self) {
+        self.results.clear();
+    }
+}
+
+// The multiply-rotate hash used by rustc, which is much faster than the
+// default SipHash for keys made of a few integers and short strings. There is
+// no risk of collision attacks that matters here, since the worst case is a
+// slower lookup.
+#[derive(Default)]
+struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let word = chunk
+                .iter()
+                .fold(0, |word, &byte| word << 8 | u64::from(byte));
+            self.add(word);
+        }
+        for &byte in chunks.remainder() {
+            self.add(u64::from(byte));
+        }
+    }
+
+    fn write_u64(&mut self, word: u64) {
+        self.add(word);
+    }
+
+    fn write_usize(&mut self, word: usize) {
+        self.add(word as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
@@ -0,0 +1,61 @@
+use crate::alloc::vec::Vec;
+use crate::interval::{self, Interval};
+use crate::{eval, IncludeBoundsMode, Version, VersionReq};
+
+/// A [`VersionReq`] with the bounds of its comparators worked out ahead of
+/// time, for matching one requirement against many versions.
+///
+/// [`VersionReq::matches`] works out what each caret, tilde, wildcard, and
+/// partial version means on every call, then checks the version against each
+/// `||` alternative in turn. `CompiledReq` instead turns the alternatives into
+/// a sorted list of disjoint intervals once, up front, so that matching a
+/// release is a binary search with a precedence comparison or two at each
+/// step. Pre-release versions, which are subject to the extra rule described
+/// for [`Prerelease`][crate::Prerelease], are matched the same way as by
+/// `matches`.
+///
+/// The result of [`matches`][CompiledReq::matches] is always the same as that
+/// of [`VersionReq::matches`].
+///
+/// # Example
+///
+/// ```
+/// use semver::{CompiledReq, Version, VersionReq};
+///
+/// let req = VersionReq::parse("^1.2.3 || ~2.1 || >=3.0.0-rc.1 <3.1").unwrap();
+/// let compiled = CompiledReq::new(req);
+///
+/// assert!(compiled.matches(&Version::parse("2.1.7").unwrap()));
+/// assert!(compiled.matches(&Version::parse("3.0.0-rc.2").unwrap()));
+/// assert!(!compiled.matches(&Version::parse("2.2.0").unwrap()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CompiledReq {
+    req: VersionReq,
+    // The versions matched by `req`, leaving aside the pre-release rule, which
+    // are exactly the releases it matches.
+    intervals: Vec<Interval>,
+}
+
+impl CompiledReq {
+    pub fn new(req: VersionReq) -> Self {
+        let intervals = interval::from_req(&req);
+        CompiledReq { req, intervals }
+    }
+
+    pub fn req(&self) -> &VersionReq {
+        &self.req
+    }
+
+    /// The same as `self.req().matches(version)`.
+    pub fn matches(&self, version: &Version) -> bool {
+        if version.pre.is_empty() {
+            interval::contains(&self.intervals, version)
+        } else {
+            // The same two checks as `matches`, in the other order, since most
+            // pre-releases are rejected by the cheaper pre-release rule.
+            eval::pre_is_allowed(&self.req, version)
+                && eval::matches_ranges(&self.req, version, IncludeBoundsMode::Exclusive)
+        }
+    }
+}
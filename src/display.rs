@@ -1,4 +1,7 @@
-use crate::{BuildMetadata, Comparator, Op, Prerelease, Version, VersionReq, VersionRange};
+use crate::{
+    BuildMetadata, Comparator, Op, PaddedVersion, PartialVersion, Prerelease, Version,
+    VersionRange, VersionReq,
+};
 use core::fmt::{self, Alignment, Debug, Display, Write};
 
 impl Display for Version {
@@ -30,6 +33,31 @@ impl Display for Version {
     }
 }
 
+impl Display for PaddedVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.text)
+    }
+}
+
+impl Display for PartialVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.major)?;
+        if let Some(minor) = &self.minor {
+            write!(formatter, ".{}", minor)?;
+            if let Some(patch) = &self.patch {
+                write!(formatter, ".{}", patch)?;
+                if !self.pre.is_empty() {
+                    write!(formatter, "-{}", self.pre)?;
+                }
+                if !self.build.is_empty() {
+                    write!(formatter, "+{}", self.build)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Display for VersionReq {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         if self.ranges.is_empty() {
@@ -74,6 +102,7 @@ impl Display for Comparator {
             Op::LessEq => "<=",
             Op::Tilde => "~",
             Op::Caret => "^",
+            Op::Pessimistic => "~>",
             Op::Wildcard => "",
             #[cfg(no_non_exhaustive)]
             Op::__NonExhaustive => unreachable!(),
@@ -1,6 +1,7 @@
 use crate::parse::Error;
 use core::fmt::{self, Debug, Display};
 
+#[derive(Copy, Clone)]
 pub(crate) enum ErrorKind {
     Empty,
     UnexpectedEnd(Position),
@@ -18,6 +19,14 @@ pub(crate) enum ErrorKind {
     ExcessiveComparators,
     #[allow(dead_code)]
     ExpectedComparator(char),
+    UnrecognizedOp,
+    // The byte offset of the pre-release's `-`, which unlike the offsets from
+    // parse_collect_errors is part of the message, the way a Position is. A
+    // u32 keeps Error the same size as a (Position, char).
+    PrereleaseNotAllowed(u32),
+    InvertedHyphenRange,
+    OperatorInHyphenRange,
+    PrereleaseOnPartial(Position),
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -25,6 +34,10 @@ pub(crate) enum Position {
     Major,
     Minor,
     Patch,
+    #[cfg(feature = "four_component")]
+    Revision,
+    #[cfg(feature = "pep440")]
+    Epoch,
     Pre,
     Build,
 }
@@ -96,6 +109,27 @@ impl Display for Error {
                     ch,
                 )
             }
+            ErrorKind::UnrecognizedOp => formatter.write_str("unrecognized comparison operator"),
+            ErrorKind::PrereleaseNotAllowed(offset) => {
+                write!(
+                    formatter,
+                    "pre-release at byte {} is not allowed in this version req",
+                    offset,
+                )
+            }
+            ErrorKind::InvertedHyphenRange => {
+                formatter.write_str("hyphen range lower bound is above its upper bound")
+            }
+            ErrorKind::OperatorInHyphenRange => {
+                formatter.write_str("comparison operator is not allowed in a hyphen range")
+            }
+            ErrorKind::PrereleaseOnPartial(pos) => {
+                write!(
+                    formatter,
+                    "pre-release must follow a patch version number, not a {}",
+                    pos,
+                )
+            }
         }
     }
 }
@@ -106,6 +140,10 @@ impl Display for Position {
             Position::Major => "major version number",
             Position::Minor => "minor version number",
             Position::Patch => "patch version number",
+            #[cfg(feature = "four_component")]
+            Position::Revision => "revision number",
+            #[cfg(feature = "pep440")]
+            Position::Epoch => "epoch",
             Position::Pre => "pre-release identifier",
             Position::Build => "build metadata",
         })
@@ -1,32 +1,51 @@
-use crate::{Comparator, Op, Version, VersionReq, VersionRange};
+use crate::alloc::string::String;
+use crate::identifier::Identifier;
+use crate::{
+    BuildMetadata, Comparator, IncludeBoundsMode, Op, PartialVersion, Prerelease, Version,
+    VersionRange, VersionReq,
+};
+use core::cmp::Ordering;
+use core::ops::Bound;
+use core::u64;
 
 pub(crate) fn matches_req(req: &VersionReq, ver: &Version) -> bool {
-    let matched_range = req.ranges.is_empty() || req.ranges.iter().any(|range| {
+    matches_req_with(req, ver, IncludeBoundsMode::Exclusive)
+}
+
+pub(crate) fn matches_req_with(req: &VersionReq, ver: &Version, mode: IncludeBoundsMode) -> bool {
+    matches_ranges(req, ver, mode) && (ver.pre.is_empty() || pre_is_allowed(req, ver))
+}
+
+// Whether some range of `req` matches `ver`, leaving aside the restriction on
+// pre-release versions.
+pub(crate) fn matches_ranges(req: &VersionReq, ver: &Version, mode: IncludeBoundsMode) -> bool {
+    let inclusive = mode == IncludeBoundsMode::Inclusive;
+    req.ranges.is_empty() || req.ranges.iter().any(|range| {
         match range {
             VersionRange::Simple(cmp) => {
-                matches_impl(&cmp, ver)
+                matches_impl(&cmp, ver) || inclusive && is_upper_bound(cmp.op, cmp, ver)
             },
             VersionRange::Hyphen(left, right) => {
-                (matches_exact(&left, ver) || matches_greater(&left, ver)) &&
-                (matches_exact(&right, ver) || matches_less(&right, ver))
+                let matches = (matches_exact(&left, ver) || matches_greater(&left, ver)) &&
+                    (matches_exact(&right, ver) || matches_less(&right, ver)
+                        || inclusive && is_upper_bound(Op::LessEq, right, ver));
+                #[cfg(feature = "trace")]
+                log::trace!("{} - {} vs {}: matches_hyphen={}", left, right, ver, matches);
+                matches
             },
             VersionRange::Intersection(comparators) => {
-                comparators.iter().all(|cmp| matches_impl(&cmp, ver))
+                comparators.iter().all(|cmp| {
+                    matches_impl(&cmp, ver) || inclusive && is_upper_bound(cmp.op, cmp, ver)
+                })
             }
         }
-    });
-
-    if !matched_range {
-        return false;
-    }
-
-    if ver.pre.is_empty() {
-        return true;
-    }
+    })
+}
 
-    // If a version has a prerelease tag (for example, 1.2.3-alpha.3) then it
-    // will only be allowed to satisfy req if at least one comparator with the
-    // same major.minor.patch also has a prerelease tag.
+// If a version has a prerelease tag (for example, 1.2.3-alpha.3) then it
+// will only be allowed to satisfy req if at least one comparator with the
+// same major.minor.patch also has a prerelease tag.
+pub(crate) fn pre_is_allowed(req: &VersionReq, ver: &Version) -> bool {
     req.ranges.iter().any(|range| {
         match range {
             VersionRange::Simple(cmp) => {
@@ -46,8 +65,19 @@ pub(crate) fn matches_comparator(cmp: &Comparator, ver: &Version) -> bool {
     matches_impl(cmp, ver) && (ver.pre.is_empty() || pre_is_compatible(cmp, ver))
 }
 
+pub(crate) fn matches_tag(tag: &PartialVersion, ver: &Version) -> bool {
+    let cmp = Comparator {
+        op: Op::Exact,
+        major: tag.major,
+        minor: tag.minor,
+        patch: tag.patch,
+        pre: tag.pre.clone(),
+    };
+    matches_exact(&cmp, ver)
+}
+
 fn matches_impl(cmp: &Comparator, ver: &Version) -> bool {
-    match cmp.op {
+    let matches = match cmp.op {
         Op::Exact | Op::Wildcard => matches_exact(cmp, ver),
         Op::Greater => matches_greater(cmp, ver),
         Op::GreaterEq => matches_exact(cmp, ver) || matches_greater(cmp, ver),
@@ -55,6 +85,27 @@ fn matches_impl(cmp: &Comparator, ver: &Version) -> bool {
         Op::LessEq => matches_exact(cmp, ver) || matches_less(cmp, ver),
         Op::Tilde => matches_tilde(cmp, ver),
         Op::Caret => matches_caret(cmp, ver),
+        Op::Pessimistic => matches_pessimistic(cmp, ver),
+        #[cfg(no_non_exhaustive)]
+        Op::__NonExhaustive => unreachable!(),
+    };
+    #[cfg(feature = "trace")]
+    log::trace!("{} vs {}: {}={}", cmp, ver, trace_name(cmp.op), matches);
+    matches
+}
+
+#[cfg(feature = "trace")]
+fn trace_name(op: Op) -> &'static str {
+    match op {
+        Op::Exact => "matches_exact",
+        Op::Greater => "matches_greater",
+        Op::GreaterEq => "matches_greater_eq",
+        Op::Less => "matches_less",
+        Op::LessEq => "matches_less_eq",
+        Op::Tilde => "matches_tilde",
+        Op::Caret => "matches_caret",
+        Op::Wildcard => "matches_wildcard",
+        Op::Pessimistic => "matches_pessimistic",
         #[cfg(no_non_exhaustive)]
         Op::__NonExhaustive => unreachable!(),
     }
@@ -80,6 +131,11 @@ fn matches_exact(cmp: &Comparator, ver: &Version) -> bool {
     ver.pre == cmp.pre
 }
 
+// Whether `ver` is greater than every version that a partial `cmp` stands
+// for, so that `>1.2` means `>=1.3.0`. Once `ver` agrees with all of the
+// components that `cmp` has, it is one of those versions and not greater,
+// hence `false` for a missing minor or patch; `>=1.2` still matches `1.2.5`
+// through matches_exact. Likewise for matches_less.
 fn matches_greater(cmp: &Comparator, ver: &Version) -> bool {
     if ver.major != cmp.major {
         return ver.major > cmp.major;
@@ -152,6 +208,21 @@ fn matches_tilde(cmp: &Comparator, ver: &Version) -> bool {
     ver.pre >= cmp.pre
 }
 
+fn matches_pessimistic(cmp: &Comparator, ver: &Version) -> bool {
+    if cmp.patch.is_some() {
+        return matches_tilde(cmp, ver);
+    }
+
+    if ver.major != cmp.major {
+        return false;
+    }
+
+    match cmp.minor {
+        None => true,
+        Some(minor) => ver.minor >= minor,
+    }
+}
+
 fn matches_caret(cmp: &Comparator, ver: &Version) -> bool {
     if ver.major != cmp.major {
         return false;
@@ -192,9 +263,236 @@ fn matches_caret(cmp: &Comparator, ver: &Version) -> bool {
     ver.pre >= cmp.pre
 }
 
+// Whether `ver` is the exclusive upper bound of `cmp` treated as `op`.
+fn is_upper_bound(op: Op, cmp: &Comparator, ver: &Version) -> bool {
+    match op_bounds(op, cmp).1 {
+        Bound::Excluded(upper) => cmp_precedence(ver, &upper) == Ordering::Equal,
+        Bound::Included(_) | Bound::Unbounded => false,
+    }
+}
+
 fn pre_is_compatible(cmp: &Comparator, ver: &Version) -> bool {
     cmp.major == ver.major
         && cmp.minor == Some(ver.minor)
         && cmp.patch == Some(ver.patch)
         && !cmp.pre.is_empty()
 }
+
+// The bounds below describe the set of versions matched by a comparator or
+// range, without regard for the pre-release restriction in matches_req. Partial
+// versions are rounded out to release boundaries: `<1.2` is `<1.2.0`, `^1.2.3`
+// is `>=1.2.3, <2.0.0`, and `~1.2` is `>=1.2.0, <1.3.0`. Bounds never carry
+// build metadata, and are compared by precedence only.
+
+pub(crate) fn comparator_bounds(cmp: &Comparator) -> (Bound<Version>, Bound<Version>) {
+    op_bounds(cmp.op, cmp)
+}
+
+pub(crate) fn range_bounds(range: &VersionRange) -> (Bound<Version>, Bound<Version>) {
+    match range {
+        VersionRange::Simple(cmp) => comparator_bounds(cmp),
+        VersionRange::Hyphen(left, right) => {
+            let (lower, _) = op_bounds(Op::GreaterEq, left);
+            let (_, upper) = op_bounds(Op::LessEq, right);
+            (lower, upper)
+        }
+        VersionRange::Intersection(comparators) => {
+            let mut lower = Bound::Unbounded;
+            let mut upper = Bound::Unbounded;
+            for cmp in comparators {
+                let (lo, hi) = comparator_bounds(cmp);
+                lower = max_lower(lower, lo);
+                upper = min_upper(upper, hi);
+            }
+            (lower, upper)
+        }
+    }
+}
+
+fn op_bounds(op: Op, cmp: &Comparator) -> (Bound<Version>, Bound<Version>) {
+    let floor = Version {
+        major: cmp.major,
+        minor: cmp.minor.unwrap_or(0),
+        patch: cmp.patch.unwrap_or(0),
+        pre: cmp.pre.clone(),
+        build: BuildMetadata::EMPTY,
+    };
+
+    // The lowest version above everything covered by the partial version.
+    let next = || match (cmp.minor, cmp.patch) {
+        (None, _) => next_major(cmp.major),
+        (Some(minor), None) => next_minor(cmp.major, minor),
+        (Some(minor), Some(patch)) => next_patch(cmp.major, minor, patch),
+    };
+
+    match op {
+        Op::Exact | Op::Wildcard => {
+            if cmp.patch.is_some() {
+                (Bound::Included(floor.clone()), Bound::Included(floor))
+            } else {
+                (Bound::Included(floor), exclusive(next()))
+            }
+        }
+        Op::Greater => {
+            if cmp.patch.is_some() {
+                (Bound::Excluded(floor), Bound::Unbounded)
+            } else {
+                let lower = match next() {
+                    Some(next) => Bound::Included(next),
+                    None => Bound::Excluded(Version::new(u64::MAX, u64::MAX, u64::MAX)),
+                };
+                (lower, Bound::Unbounded)
+            }
+        }
+        Op::GreaterEq => (Bound::Included(floor), Bound::Unbounded),
+        Op::Less => (Bound::Unbounded, Bound::Excluded(floor)),
+        Op::LessEq => {
+            if cmp.patch.is_some() {
+                (Bound::Unbounded, Bound::Included(floor))
+            } else {
+                (Bound::Unbounded, exclusive(next()))
+            }
+        }
+        Op::Tilde => {
+            let upper = match cmp.minor {
+                Some(minor) => next_minor(cmp.major, minor),
+                None => next_major(cmp.major),
+            };
+            (Bound::Included(floor), exclusive(upper))
+        }
+        Op::Caret => {
+            let upper = match (cmp.minor, cmp.patch) {
+                (None, _) => next_major(cmp.major),
+                _ if cmp.major > 0 => next_major(cmp.major),
+                (Some(minor), None) => next_minor(0, minor),
+                (Some(0), Some(patch)) => next_patch(0, 0, patch),
+                (Some(minor), Some(_)) => next_minor(0, minor),
+            };
+            (Bound::Included(floor), exclusive(upper))
+        }
+        Op::Pessimistic => {
+            let upper = match (cmp.minor, cmp.patch) {
+                (Some(minor), Some(_)) => next_minor(cmp.major, minor),
+                _ => next_major(cmp.major),
+            };
+            (Bound::Included(floor), exclusive(upper))
+        }
+        #[cfg(no_non_exhaustive)]
+        Op::__NonExhaustive => unreachable!(),
+    }
+}
+
+fn next_major(major: u64) -> Option<Version> {
+    major.checked_add(1).map(|major| Version::new(major, 0, 0))
+}
+
+fn next_minor(major: u64, minor: u64) -> Option<Version> {
+    match minor.checked_add(1) {
+        Some(minor) => Some(Version::new(major, minor, 0)),
+        None => next_major(major),
+    }
+}
+
+pub(crate) fn next_patch(major: u64, minor: u64, patch: u64) -> Option<Version> {
+    match patch.checked_add(1) {
+        Some(patch) => Some(Version::new(major, minor, patch)),
+        None => next_minor(major, minor),
+    }
+}
+
+fn exclusive(upper: Option<Version>) -> Bound<Version> {
+    match upper {
+        Some(upper) => Bound::Excluded(upper),
+        None => Bound::Unbounded,
+    }
+}
+
+/// The lowest version of greater precedence than `ver`, if there is one.
+pub(crate) fn successor(ver: &Version) -> Option<Version> {
+    if ver.pre.is_empty() {
+        let mut next = next_patch(ver.major, ver.minor, ver.patch)?;
+        next.pre = Prerelease {
+            identifier: unsafe { Identifier::new_unchecked("0") },
+        };
+        Some(next)
+    } else {
+        let mut next = Version::new(ver.major, ver.minor, ver.patch);
+        let mut pre = String::from(ver.pre.as_str());
+        pre.push_str(".0");
+        next.pre = Prerelease {
+            identifier: unsafe { Identifier::new_unchecked(&pre) },
+        };
+        Some(next)
+    }
+}
+
+/// The greatest version of lower precedence than `ver`, if there is one. This
+/// is the inverse of `successor`.
+pub(crate) fn predecessor(ver: &Version) -> Option<Version> {
+    if ver.pre.as_str() == "0" {
+        let max = u64::MAX;
+        return match (ver.major, ver.minor, ver.patch) {
+            (0, 0, 0) => None,
+            (major, 0, 0) => Some(Version::new(major - 1, max, max)),
+            (major, minor, 0) => Some(Version::new(major, minor - 1, max)),
+            (major, minor, patch) => Some(Version::new(major, minor, patch - 1)),
+        };
+    }
+    let pre = ver.pre.as_str();
+    if !pre.ends_with(".0") {
+        return None;
+    }
+    let pre = &pre[..pre.len() - 2];
+    let mut prev = Version::new(ver.major, ver.minor, ver.patch);
+    prev.pre = Prerelease {
+        identifier: unsafe { Identifier::new_unchecked(pre) },
+    };
+    Some(prev)
+}
+
+pub(crate) fn cmp_precedence(a: &Version, b: &Version) -> Ordering {
+    a.major
+        .cmp(&b.major)
+        .then(a.minor.cmp(&b.minor))
+        .then(a.patch.cmp(&b.patch))
+        .then_with(|| a.pre.cmp(&b.pre))
+}
+
+pub(crate) fn max_lower(a: Bound<Version>, b: Bound<Version>) -> Bound<Version> {
+    let ordering = match (bound_version(&a), bound_version(&b)) {
+        (None, _) => return b,
+        (_, None) => return a,
+        (Some(x), Some(y)) => cmp_precedence(x, y),
+    };
+    match ordering {
+        Ordering::Less => b,
+        Ordering::Greater => a,
+        Ordering::Equal => match a {
+            Bound::Excluded(_) => a,
+            _ => b,
+        },
+    }
+}
+
+pub(crate) fn min_upper(a: Bound<Version>, b: Bound<Version>) -> Bound<Version> {
+    let ordering = match (bound_version(&a), bound_version(&b)) {
+        (None, _) => return b,
+        (_, None) => return a,
+        (Some(x), Some(y)) => cmp_precedence(x, y),
+    };
+    match ordering {
+        Ordering::Less => a,
+        Ordering::Greater => b,
+        Ordering::Equal => match a {
+            Bound::Excluded(_) => a,
+            _ => b,
+        },
+    }
+}
+
+pub(crate) fn bound_version(bound: &Bound<Version>) -> Option<&Version> {
+    match bound {
+        Bound::Included(ver) | Bound::Excluded(ver) => Some(ver),
+        Bound::Unbounded => None,
+    }
+}
@@ -1,10 +1,15 @@
+#[cfg(all(not(feature = "std"), not(no_alloc_crate)))]
+use crate::alloc::vec;
 use crate::{backport::*, VersionRange};
 use crate::identifier::Identifier;
-use crate::{BuildMetadata, Prerelease, VersionReq};
+use crate::{eval, interval};
+use crate::{
+    BuildMetadata, Comparator, Op, PaddedVersion, Prerelease, TotalVersion, Version, VersionReq,
+};
 use core::cmp::Ordering;
 use core::hash::{Hash, Hasher};
 use core::iter::FromIterator;
-use core::ops::Deref;
+use core::ops::{Deref, Range, RangeInclusive};
 
 impl Default for Identifier {
     fn default() -> Self {
@@ -145,6 +150,44 @@ impl Ord for BuildMetadata {
     }
 }
 
+impl PartialOrd for TotalVersion {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, rhs))
+    }
+}
+
+impl Ord for TotalVersion {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp_with_build(&rhs.0)
+    }
+}
+
+impl PartialEq for PaddedVersion {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.version == rhs.version
+    }
+}
+
+impl Eq for PaddedVersion {}
+
+impl PartialOrd for PaddedVersion {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, rhs))
+    }
+}
+
+impl Ord for PaddedVersion {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.version.cmp(&rhs.version)
+    }
+}
+
+impl Hash for PaddedVersion {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.version.hash(hasher);
+    }
+}
+
 impl FromIterator<VersionRange> for VersionReq {
     fn from_iter<I>(iter: I) -> Self
     where
@@ -154,3 +197,72 @@ impl FromIterator<VersionRange> for VersionReq {
         VersionReq { ranges }
     }
 }
+
+/// The hyphen range `start - end`, or `<0.0.0`, which matches nothing, if
+/// `start` has greater precedence than `end`.
+///
+/// The requirement matches a release with no build metadata exactly when the
+/// range contains it. Otherwise the usual rules of [`VersionReq::matches`]
+/// apply: pre-releases match only those with the same major.minor.patch as
+/// `start` or `end`, and build metadata is ignored, whereas the range orders
+/// both as described for [`Version`].
+impl From<RangeInclusive<Version>> for VersionReq {
+    fn from(range: RangeInclusive<Version>) -> Self {
+        let (start, end) = range.into_inner();
+        if eval::cmp_precedence(&start, &end) == Ordering::Greater {
+            return interval::to_req(Vec::new());
+        }
+        start.hyphen_req(&end)
+    }
+}
+
+/// The requirement `>=start <end`, or `<0.0.0`, which matches nothing, if
+/// `start` does not have lower precedence than `end`.
+///
+/// As for `RangeInclusive`, this matches a release with no build metadata
+/// exactly when the range contains it.
+impl From<Range<Version>> for VersionReq {
+    fn from(range: Range<Version>) -> Self {
+        if eval::cmp_precedence(&range.start, &range.end) != Ordering::Less {
+            return interval::to_req(Vec::new());
+        }
+        let comparators = vec![
+            range.start.comparator(Op::GreaterEq),
+            range.end.comparator(Op::Less),
+        ];
+        VersionReq {
+            ranges: vec![VersionRange::Intersection(comparators)],
+        }
+    }
+}
+
+impl From<(u64, u64, u64)> for Version {
+    fn from((major, minor, patch): (u64, u64, u64)) -> Self {
+        Version::new(major, minor, patch)
+    }
+}
+
+/// A comparator equals a version if it pins exactly that version: its operator
+/// is `=` and its major, minor, patch, and pre-release are all present and the
+/// same as the version's. So `=1.2.3` equals `1.2.3` and `1.2.3+build`, while
+/// `^1.2.3` and `=1.2` match `1.2.3` without being equal to it.
+///
+/// A wildcard comparator such as `1.2.*` never equals a version, since the
+/// component that the wildcard stands for is always missing, so it matches a
+/// range of versions rather than pinning one.
+impl PartialEq<Version> for Comparator {
+    fn eq(&self, version: &Version) -> bool {
+        self.op == Op::Exact
+            && self.major == version.major
+            && self.minor == Some(version.minor)
+            && self.patch == Some(version.patch)
+            && self.pre == version.pre
+    }
+}
+
+/// The same as comparing the comparator to the version, the other way around.
+impl PartialEq<Comparator> for Version {
+    fn eq(&self, comparator: &Comparator) -> bool {
+        comparator == self
+    }
+}
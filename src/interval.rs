@@ -0,0 +1,493 @@
+// Set arithmetic on the versions matched by a VersionReq, treating each
+// VersionRange as an interval of version precedence as computed by
+// eval::range_bounds. A set is kept as a sorted list of disjoint, non-empty
+// intervals.
+//
+// Like range_bounds, this works on precedence alone and does not model the
+// pre-release restriction in matches_req. Converting a set back to a
+// VersionReq produces comparators whose pre-release tags come only from the
+// interval endpoints, so pre-release versions strictly inside a range are
+// matched only as far as matches_req allows.
+
+use crate::alloc::string::String;
+#[cfg(all(not(feature = "std"), not(no_alloc_crate)))]
+use crate::alloc::vec;
+use crate::alloc::vec::Vec;
+use crate::eval::{
+    bound_version, cmp_precedence, comparator_bounds, max_lower, min_upper, next_patch,
+    range_bounds, successor,
+};
+use crate::identifier::Identifier;
+use crate::{Comparator, Op, Prerelease, Version, VersionRange, VersionReq};
+use core::cmp::Ordering;
+use core::ops::Bound;
+use core::u64;
+
+pub(crate) type Interval = (Bound<Version>, Bound<Version>);
+
+pub(crate) fn from_req(req: &VersionReq) -> Vec<Interval> {
+    if req.ranges.is_empty() {
+        return vec![(Bound::Unbounded, Bound::Unbounded)];
+    }
+    let intervals = req.ranges.iter().map(range_bounds).collect();
+    normalize(intervals)
+}
+
+pub(crate) fn from_comparator(cmp: &Comparator) -> Vec<Interval> {
+    normalize(vec![comparator_bounds(cmp)])
+}
+
+pub(crate) fn to_req(set: Vec<Interval>) -> VersionReq {
+    if set.is_empty() {
+        return VersionReq {
+            ranges: vec![VersionRange::Simple(comparator(
+                Op::Less,
+                &Version::new(0, 0, 0),
+            ))],
+        };
+    }
+    let mut ranges = Vec::new();
+    for (lower, upper) in set {
+        let lower = match lower {
+            Bound::Included(ver) => Some((Op::GreaterEq, ver)),
+            Bound::Excluded(ver) => Some((Op::Greater, ver)),
+            Bound::Unbounded => None,
+        };
+        let upper = match upper {
+            Bound::Included(ver) => Some((Op::LessEq, ver)),
+            Bound::Excluded(ver) => Some((Op::Less, ver)),
+            Bound::Unbounded => None,
+        };
+        let range = match (lower, upper) {
+            (None, None) => return VersionReq::STAR,
+            (Some((op, ver)), None) | (None, Some((op, ver))) => {
+                VersionRange::Simple(comparator(op, &ver))
+            }
+            (Some((Op::GreaterEq, ref lo)), Some((Op::LessEq, ref hi)))
+                if cmp_precedence(lo, hi) == Ordering::Equal =>
+            {
+                VersionRange::Simple(comparator(Op::Exact, lo))
+            }
+            (Some((lo_op, lo)), Some((hi_op, hi))) => {
+                VersionRange::Intersection(vec![comparator(lo_op, &lo), comparator(hi_op, &hi)])
+            }
+        };
+        ranges.push(range);
+    }
+    VersionReq { ranges }
+}
+
+pub(crate) fn complement(set: &[Interval]) -> Vec<Interval> {
+    let mut out = Vec::new();
+    let mut lower = Bound::Unbounded;
+    let mut open = true;
+    for (lo, hi) in set {
+        if let Some(upper) = flip(lo) {
+            if !is_empty(&lower, &upper) {
+                out.push((lower.clone(), upper));
+            }
+        }
+        match flip(hi) {
+            Some(next) => lower = next,
+            None => open = false,
+        }
+    }
+    if open {
+        out.push((lower, Bound::Unbounded));
+    }
+    out
+}
+
+pub(crate) fn intersect(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    let mut out = Vec::new();
+    for (a_lo, a_hi) in a {
+        for (b_lo, b_hi) in b {
+            let lower = max_lower(a_lo.clone(), b_lo.clone());
+            let upper = min_upper(a_hi.clone(), b_hi.clone());
+            if !is_empty(&lower, &upper) {
+                out.push((lower, upper));
+            }
+        }
+    }
+    normalize(out)
+}
+
+// Whether every version matched by `a` is also matched by `b`. Beyond what
+// their intervals cover, this accounts for the pre-release rule: a version of
+// `a` outside of `b` counts only if it is a release or a pre-release of a
+// major.minor.patch that some comparator of `a` gives a pre-release, and such
+// a pre-release is matched by `b` only if some comparator of `b` does the same.
+pub(crate) fn is_subset(a: &VersionReq, b: &VersionReq) -> bool {
+    let a_set = from_req(a);
+    let outside = intersect(&a_set, &complement(&from_req(b)));
+    if outside.iter().any(has_release) {
+        return false;
+    }
+    let b_pre = pre_triples(b);
+    pre_triples(a).into_iter().all(|triple| {
+        let pre = [prereleases_of(triple)];
+        if b_pre.contains(&triple) {
+            intersect(&outside, &pre).is_empty()
+        } else {
+            intersect(&a_set, &pre).is_empty()
+        }
+    })
+}
+
+// The major.minor.patch of every comparator in `req` with a pre-release.
+fn pre_triples(req: &VersionReq) -> Vec<(u64, u64, u64)> {
+    let mut triples = Vec::new();
+    for cmp in req.ranges.iter().flat_map(range_comparators) {
+        if !cmp.pre.is_empty() {
+            triples.push((cmp.major, cmp.minor.unwrap_or(0), cmp.patch.unwrap_or(0)));
+        }
+    }
+    triples
+}
+
+// The pre-releases of a major.minor.patch, from its `-0` up to the release.
+fn prereleases_of((major, minor, patch): (u64, u64, u64)) -> Interval {
+    let release = Version::new(major, minor, patch);
+    let mut zero = release.clone();
+    zero.pre = Prerelease {
+        identifier: unsafe { Identifier::new_unchecked("0") },
+    };
+    (Bound::Included(zero), Bound::Excluded(release))
+}
+
+fn has_release((lo, hi): &Interval) -> bool {
+    match first_release(lo) {
+        Some(release) => !is_above(&release, hi),
+        None => false,
+    }
+}
+
+// The version matched by `req` that is closest to `ver`, preferring to round
+// up into the next interval; see Version::clamp_to_req.
+pub(crate) fn clamp(req: &VersionReq, ver: &Version) -> Option<Version> {
+    let set = from_req(req);
+    let mut candidates = Vec::new();
+    match set.iter().position(|(_, hi)| !is_above(ver, hi)) {
+        Some(i) => {
+            if !is_below(ver, &set[i].0) {
+                // Within the interval yet not matched, so `ver` must be a
+                // pre-release. Round up to its release.
+                candidates.push(Version::new(ver.major, ver.minor, ver.patch));
+            }
+            for (lo, _) in &set[i..] {
+                candidates.extend(lowest_release(lo));
+            }
+            for interval in set[..i].iter().rev() {
+                candidates.extend(highest(interval));
+            }
+        }
+        None => {
+            for interval in set.iter().rev() {
+                candidates.extend(highest(interval));
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .find(|candidate| req.matches(candidate))
+}
+
+// Whether some version matches both comparators. If the intersection of their
+// bounds holds a release, the greatest release or the lowest version in it is
+// one; otherwise it holds only pre-releases of one major.minor.patch, and
+// the lowest of those is the one that a comparator could allow.
+pub(crate) fn comparators_intersect(a: &Comparator, b: &Comparator) -> bool {
+    intersect(&from_comparator(a), &from_comparator(b))
+        .iter()
+        .flat_map(|(lo, hi)| {
+            let mut candidates = lowest(lo);
+            candidates.extend(greatest_release(hi));
+            candidates
+        })
+        .any(|candidate| a.matches(&candidate) && b.matches(&candidate))
+}
+
+// The least version matched by `req`; see VersionReq::sample_min. The least
+// release in an interval is its first release, and the least pre-release is
+// either its lowest version or, for a major.minor.patch that some comparator
+// gives a pre-release, the `-0` pre-release of that major.minor.patch.
+pub(crate) fn sample_min(req: &VersionReq) -> Option<Version> {
+    let mut candidates = Vec::new();
+    for (lo, _) in from_req(req) {
+        candidates.extend(lowest(&lo));
+        candidates.extend(first_release(&lo));
+    }
+    // A partial comparator such as `^0.0` matches a pre-release by its
+    // major.minor.patch, outside of its interval, so the pre-releases named
+    // by the other comparators are candidates too.
+    for cmp in req.ranges.iter().flat_map(range_comparators) {
+        if !cmp.pre.is_empty() {
+            let mut zero = Version::new(cmp.major, cmp.minor.unwrap_or(0), cmp.patch.unwrap_or(0));
+            let mut exact = zero.clone();
+            exact.pre = cmp.pre.clone();
+            let mut following = zero.clone();
+            let mut pre = String::from(cmp.pre.as_str());
+            pre.push_str(".0");
+            following.pre = Prerelease::new(&pre).unwrap();
+            zero.pre = Prerelease {
+                identifier: unsafe { Identifier::new_unchecked("0") },
+            };
+            candidates.push(zero);
+            candidates.push(exact);
+            candidates.push(following);
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|candidate| req.matches(candidate))
+        .min_by(cmp_precedence)
+}
+
+// The greatest version matched by `req`, or the greatest release below an
+// exclusive upper bound; see VersionReq::sample_max.
+pub(crate) fn sample_max(req: &VersionReq) -> Option<Version> {
+    from_req(req)
+        .iter()
+        .flat_map(|(lo, hi)| {
+            let mut candidates = greatest_release(hi);
+            candidates.extend(lowest(lo));
+            candidates
+        })
+        .filter(|candidate| req.matches(candidate))
+        .max_by(cmp_precedence)
+}
+
+// The versions on and around every bound of every comparator in `req`; see
+// VersionReq::boundary_versions.
+pub(crate) fn boundary_versions(req: &VersionReq) -> Vec<Version> {
+    let mut bounds = Vec::new();
+    for range in &req.ranges {
+        match range {
+            VersionRange::Simple(cmp) => bounds.push(comparator_bounds(cmp)),
+            VersionRange::Hyphen(..) => bounds.push(range_bounds(range)),
+            VersionRange::Intersection(comparators) => {
+                bounds.extend(comparators.iter().map(comparator_bounds));
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    for (lo, hi) in &bounds {
+        for ver in bound_version(lo).into_iter().chain(bound_version(hi)) {
+            out.extend(greatest_release(&Bound::Excluded(ver.clone())));
+            if ver.pre.is_empty() {
+                let mut zero = ver.clone();
+                zero.pre = Prerelease {
+                    identifier: unsafe { Identifier::new_unchecked("0") },
+                };
+                out.push(zero);
+                out.push(ver.clone());
+                out.extend(next_patch(ver.major, ver.minor, ver.patch));
+            } else {
+                out.push(ver.clone());
+                out.extend(successor(ver));
+                out.push(Version::new(ver.major, ver.minor, ver.patch));
+            }
+        }
+    }
+    out.sort_by(cmp_precedence);
+    out.dedup();
+    out
+}
+
+fn lowest(lower: &Bound<Version>) -> Vec<Version> {
+    match lower {
+        Bound::Included(lo) => vec![lo.clone()],
+        Bound::Excluded(lo) if lo.pre.is_empty() => next_patch(lo.major, lo.minor, lo.patch)
+            .into_iter()
+            .collect(),
+        Bound::Excluded(lo) => successor(lo).into_iter().collect(),
+        Bound::Unbounded => {
+            let mut zero = Version::new(0, 0, 0);
+            zero.pre = Prerelease {
+                identifier: unsafe { Identifier::new_unchecked("0") },
+            };
+            vec![zero, Version::new(0, 0, 0)]
+        }
+    }
+}
+
+fn greatest_release(upper: &Bound<Version>) -> Vec<Version> {
+    let max = u64::MAX;
+    match upper {
+        Bound::Included(hi) => vec![hi.clone()],
+        Bound::Excluded(hi) => match (hi.major, hi.minor, hi.patch) {
+            (0, 0, 0) => Vec::new(),
+            (major, 0, 0) => vec![Version::new(major - 1, max, max)],
+            (major, minor, 0) => vec![Version::new(major, minor - 1, max)],
+            (major, minor, patch) => vec![Version::new(major, minor, patch - 1)],
+        },
+        Bound::Unbounded => vec![Version::new(max, max, max)],
+    }
+}
+
+// Whether `ver` is in some interval of the normalized `set`. Since the
+// intervals are sorted and disjoint, only the last one that starts at or below
+// `ver` can contain it, and it is found by binary search.
+pub(crate) fn contains(set: &[Interval], ver: &Version) -> bool {
+    let mut lo = 0;
+    let mut hi = set.len();
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if is_below(ver, &set[mid].0) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo > 0 && !is_above(ver, &set[lo - 1].1)
+}
+
+fn is_below(ver: &Version, lower: &Bound<Version>) -> bool {
+    match lower {
+        Bound::Included(lo) => cmp_precedence(ver, lo) == Ordering::Less,
+        Bound::Excluded(lo) => cmp_precedence(ver, lo) != Ordering::Greater,
+        Bound::Unbounded => false,
+    }
+}
+
+fn is_above(ver: &Version, upper: &Bound<Version>) -> bool {
+    match upper {
+        Bound::Included(hi) => cmp_precedence(ver, hi) == Ordering::Greater,
+        Bound::Excluded(hi) => cmp_precedence(ver, hi) != Ordering::Less,
+        Bound::Unbounded => false,
+    }
+}
+
+// The lowest release above a lower bound, which unlike lowest_release is never
+// a pre-release.
+fn first_release(lower: &Bound<Version>) -> Option<Version> {
+    match lower {
+        Bound::Included(lo) if !lo.pre.is_empty() => {
+            Some(Version::new(lo.major, lo.minor, lo.patch))
+        }
+        _ => lowest_release(lower),
+    }
+}
+
+fn range_comparators(range: &VersionRange) -> Vec<&Comparator> {
+    match range {
+        VersionRange::Simple(cmp) => vec![cmp],
+        VersionRange::Hyphen(left, right) => vec![left, right],
+        VersionRange::Intersection(comparators) => comparators.iter().collect(),
+    }
+}
+
+fn lowest_release(lower: &Bound<Version>) -> Option<Version> {
+    match lower {
+        Bound::Included(lo) => Some(lo.clone()),
+        Bound::Excluded(lo) if lo.pre.is_empty() => next_patch(lo.major, lo.minor, lo.patch),
+        Bound::Excluded(lo) => Some(Version::new(lo.major, lo.minor, lo.patch)),
+        Bound::Unbounded => Some(Version::new(0, 0, 0)),
+    }
+}
+
+// An inclusive upper bound if there is one, otherwise the interval's lowest
+// release, since an exclusive upper bound has no greatest version below it.
+fn highest(interval: &Interval) -> Vec<Version> {
+    let mut candidates = Vec::new();
+    if let Bound::Included(hi) = &interval.1 {
+        candidates.push(hi.clone());
+    }
+    candidates.extend(lowest_release(&interval.0));
+    candidates
+}
+
+fn normalize(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|(lo, hi)| !is_empty(lo, hi));
+    intervals.sort_by(|a, b| cmp_lower(&a.0, &b.0));
+
+    let mut out: Vec<Interval> = Vec::new();
+    for (lo, hi) in intervals {
+        if let Some(last) = out.last_mut() {
+            if connects(&last.1, &lo) {
+                last.1 = max_upper(last.1.clone(), hi);
+                continue;
+            }
+        }
+        out.push((lo, hi));
+    }
+    out
+}
+
+fn max_upper(a: Bound<Version>, b: Bound<Version>) -> Bound<Version> {
+    let ordering = match (bound_version(&a), bound_version(&b)) {
+        (None, _) => return a,
+        (_, None) => return b,
+        (Some(x), Some(y)) => cmp_precedence(x, y),
+    };
+    match ordering {
+        Ordering::Less => b,
+        Ordering::Greater => a,
+        Ordering::Equal => match a {
+            Bound::Included(_) => a,
+            _ => b,
+        },
+    }
+}
+
+fn flip(bound: &Bound<Version>) -> Option<Bound<Version>> {
+    match bound {
+        Bound::Included(ver) => Some(Bound::Excluded(ver.clone())),
+        Bound::Excluded(ver) => Some(Bound::Included(ver.clone())),
+        Bound::Unbounded => None,
+    }
+}
+
+pub(crate) fn is_empty(lower: &Bound<Version>, upper: &Bound<Version>) -> bool {
+    match (bound_version(lower), bound_version(upper)) {
+        (Some(lo), Some(hi)) => match cmp_precedence(lo, hi) {
+            Ordering::Less => false,
+            Ordering::Equal => match (lower, upper) {
+                (Bound::Included(_), Bound::Included(_)) => false,
+                _ => true,
+            },
+            Ordering::Greater => true,
+        },
+        _ => false,
+    }
+}
+
+// Whether an interval ending at `upper` overlaps or abuts one starting at
+// `lower`, so that the two can be merged.
+fn connects(upper: &Bound<Version>, lower: &Bound<Version>) -> bool {
+    match (bound_version(upper), bound_version(lower)) {
+        (Some(hi), Some(lo)) => match cmp_precedence(hi, lo) {
+            Ordering::Less => false,
+            Ordering::Equal => match (upper, lower) {
+                (Bound::Excluded(_), Bound::Excluded(_)) => false,
+                _ => true,
+            },
+            Ordering::Greater => true,
+        },
+        _ => true,
+    }
+}
+
+fn cmp_lower(a: &Bound<Version>, b: &Bound<Version>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(x), Bound::Excluded(y)) => cmp_precedence(x, y).then(Ordering::Less),
+        (Bound::Excluded(x), Bound::Included(y)) => cmp_precedence(x, y).then(Ordering::Greater),
+        (Bound::Included(x), Bound::Included(y)) | (Bound::Excluded(x), Bound::Excluded(y)) => {
+            cmp_precedence(x, y)
+        }
+    }
+}
+
+fn comparator(op: Op, ver: &Version) -> Comparator {
+    Comparator {
+        op,
+        major: ver.major,
+        minor: Some(ver.minor),
+        patch: Some(ver.patch),
+        pre: ver.pre.clone(),
+    }
+}
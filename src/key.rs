@@ -0,0 +1,111 @@
+// Byte encoding of a version's precedence, such that comparing two encodings
+// as byte strings orders them the same as comparing the versions by
+// precedence. Build metadata is not encoded.
+//
+//     major minor patch   big-endian u64 each
+//     RELEASE             if pre is empty, otherwise
+//     PRERELEASE, then for each dot-separated identifier either:
+//         NUMERIC, length as big-endian u64, digits
+//         ALPHANUMERIC, bytes, END
+//
+// Numeric identifiers have no leading zeros so comparing their length and
+// then their digits compares them numerically, and END sorts before every
+// character allowed in an identifier so a shorter alphanumeric identifier
+// sorts before any longer one it is a prefix of.
+
+use crate::alloc::string::String;
+use crate::alloc::vec::Vec;
+use crate::{Prerelease, Version};
+
+const PRERELEASE: u8 = 0;
+const RELEASE: u8 = 1;
+const NUMERIC: u8 = 1;
+const ALPHANUMERIC: u8 = 2;
+const END: u8 = 0;
+
+pub(crate) fn encode(ver: &Version) -> Vec<u8> {
+    let mut key = Vec::with_capacity(25 + ver.pre.len() * 2);
+    push_number(&mut key, ver.major);
+    push_number(&mut key, ver.minor);
+    push_number(&mut key, ver.patch);
+    if ver.pre.is_empty() {
+        key.push(RELEASE);
+        return key;
+    }
+    key.push(PRERELEASE);
+    for identifier in ver.pre.split('.') {
+        if identifier.bytes().all(|b| b.is_ascii_digit()) {
+            key.push(NUMERIC);
+            push_number(&mut key, identifier.len() as u64);
+            key.extend_from_slice(identifier.as_bytes());
+        } else {
+            key.push(ALPHANUMERIC);
+            key.extend_from_slice(identifier.as_bytes());
+            key.push(END);
+        }
+    }
+    key
+}
+
+pub(crate) fn decode(key: &[u8]) -> Option<Version> {
+    let (major, rest) = number(key)?;
+    let (minor, rest) = number(rest)?;
+    let (patch, rest) = number(rest)?;
+    let mut ver = Version::new(major, minor, patch);
+
+    let (&kind, mut rest) = rest.split_first()?;
+    match kind {
+        RELEASE if rest.is_empty() => return Some(ver),
+        PRERELEASE if !rest.is_empty() => {}
+        _ => return None,
+    }
+
+    let mut pre = String::new();
+    while let Some((&kind, after)) = rest.split_first() {
+        let identifier = match kind {
+            NUMERIC => {
+                let (len, after) = number(after)?;
+                if (after.len() as u64) < len {
+                    return None;
+                }
+                let (digits, after) = after.split_at(len as usize);
+                rest = after;
+                digits
+            }
+            ALPHANUMERIC => {
+                let end = after.iter().position(|&b| b == END)?;
+                rest = &after[end + 1..];
+                &after[..end]
+            }
+            _ => return None,
+        };
+        if !pre.is_empty() {
+            pre.push('.');
+        }
+        pre.push_str(core::str::from_utf8(identifier).ok()?);
+    }
+    ver.pre = Prerelease::new(&pre).ok()?;
+
+    // Reject keys that decode to a valid version but are not the encoding of
+    // it, such as a numeric identifier tagged as alphanumeric.
+    if encode(&ver) == key {
+        Some(ver)
+    } else {
+        None
+    }
+}
+
+fn push_number(key: &mut Vec<u8>, value: u64) {
+    for shift in (0..8).rev() {
+        key.push((value >> (shift * 8)) as u8);
+    }
+}
+
+fn number(key: &[u8]) -> Option<(u64, &[u8])> {
+    if key.len() < 8 {
+        return None;
+    }
+    let (bytes, rest) = key.split_at(8);
+    let value = bytes.iter().fold(0, |value, &b| value << 8 | b as u64);
+    Some((value, rest))
+}
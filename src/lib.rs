@@ -90,24 +90,61 @@
 extern crate alloc;
 
 mod backport;
+mod builder;
+mod compiled;
 mod display;
 mod error;
 mod eval;
 mod identifier;
 mod impls;
+mod interval;
+mod key;
 mod parse;
+mod pool;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "std")]
+mod cache;
+
+#[cfg(feature = "pep440")]
+mod pep440;
+
+#[cfg(feature = "four_component")]
+mod quad;
 
 #[cfg(feature = "serde")]
 mod serde;
 
+use crate::alloc::collections::BTreeMap;
+use crate::alloc::string::String;
 use crate::alloc::vec::Vec;
+#[cfg(all(not(feature = "std"), not(no_alloc_crate)))]
+use crate::alloc::{format, vec};
 use crate::identifier::Identifier;
+use core::cmp::Ordering;
+use core::fmt::Write;
+use core::mem;
+use core::ops::{Bound, Range};
 use core::str::FromStr;
 
 #[allow(unused_imports)]
 use crate::backport::*;
 
-pub use crate::parse::Error;
+pub use crate::builder::VersionBuilder;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::cache::MatchCache;
+pub use crate::compiled::CompiledReq;
+pub use crate::parse::{Error, VersionReqParser};
+#[cfg(feature = "pep440")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "pep440")))]
+pub use crate::pep440::PepVersion;
+pub use crate::pool::{VersionHandle, VersionPool};
+#[cfg(feature = "four_component")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "four_component")))]
+pub use crate::quad::QuadVersion;
 
 /// **SemVer version** as defined by <https://semver.org>.
 ///
@@ -157,7 +194,20 @@ pub use crate::parse::Error;
 ///   - Any numeric identifier is always less than any non-numeric
 ///     identifier:&ensp;`1.0.0-pre.1` is less than `1.0.0-pre.x`.
 ///
+/// - Versions that differ only in build metadata are equal in SemVer
+///   precedence, which is all that [`VersionReq::matches`] looks at. So that
+///   `Ord` agrees with `Eq`, they are ordered by their build metadata as
+///   described for [`BuildMetadata`], with no build metadata lowest:&ensp;
+///   `1.0.0` is less than `1.0.0+a`, which is less than `1.0.0+b`.
+///
 /// Example:&ensp;`1.0.0-alpha`&ensp;&lt;&ensp;`1.0.0-alpha.1`&ensp;&lt;&ensp;`1.0.0-alpha.beta`&ensp;&lt;&ensp;`1.0.0-beta`&ensp;&lt;&ensp;`1.0.0-beta.2`&ensp;&lt;&ensp;`1.0.0-beta.11`&ensp;&lt;&ensp;`1.0.0-rc.1`&ensp;&lt;&ensp;`1.0.0`
+///
+/// # Size
+///
+/// The pre-release and build metadata each take the size of one pointer, and
+/// are stored inline without a heap allocation when they are empty or up to 8
+/// bytes long, so on 64-bit targets `Version` is 40 bytes. `Option<Version>`
+/// is the same size.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Version {
     pub major: u64,
@@ -167,12 +217,90 @@ pub struct Version {
     pub build: BuildMetadata,
 }
 
+/// A [`Version`] ordered by SemVer precedence, with ties between versions
+/// that differ only in build metadata broken by comparing the build metadata
+/// strings lexicographically.
+///
+/// This is the order of [`Version::cmp_with_build`], as an [`Ord`] impl for
+/// use as the key of a `BTreeSet` or `BTreeMap`. `Version`'s own `Ord` is left
+/// as it is.
+///
+/// ```
+/// use semver::{TotalVersion, Version};
+/// use std::collections::BTreeSet;
+///
+/// let set: BTreeSet<TotalVersion> = ["1.0.0+10", "1.0.0+9", "1.0.0-rc.1"]
+///     .iter()
+///     .map(|v| TotalVersion(Version::parse(v).unwrap()))
+///     .collect();
+/// let sorted: Vec<String> = set.iter().map(|v| v.0.to_string()).collect();
+/// assert_eq!(sorted, ["1.0.0-rc.1", "1.0.0+10", "1.0.0+9"]);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TotalVersion(pub Version);
+
+/// A [`Version`] parsed by [`Version::parse_preserve_zeros`], which keeps the
+/// original text so that it displays the same as it was written.
+///
+/// Comparisons, including equality and hashing, look only at the numeric
+/// version, so `01.02.03` is equal to `1.2.3`.
+///
+/// ```
+/// use semver::Version;
+///
+/// let padded = Version::parse_preserve_zeros("01.02.03").unwrap();
+/// assert_eq!(*padded.version(), Version::new(1, 2, 3));
+/// assert_eq!(padded.to_string(), "01.02.03");
+/// ```
+#[derive(Clone, Debug)]
+pub struct PaddedVersion {
+    version: Version,
+    text: String,
+}
+
+/// The part of `git describe` output that follows the tag, as returned by
+/// [`Version::parse_git_describe`]. For `1.2.3-4-gabc1234` this is 4 commits
+/// ahead of the tag, at commit `abc1234`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GitDescribe {
+    pub commits_ahead: u64,
+    /// The abbreviated commit hash, without the `g` that precedes it.
+    pub sha: String,
+}
+
+/// A version in which the minor and patch numbers may be left out, such as
+/// `1.2`, keeping track of which components were written.
+///
+/// This is the partial version that a [`Comparator`] holds, without the
+/// operator. A pre-release and build metadata are allowed only after an
+/// explicit patch number, and wildcards are not allowed.
+///
+/// ```
+/// use semver::PartialVersion;
+///
+/// let partial = PartialVersion::parse("1.2").unwrap();
+/// assert_eq!(partial.minor, Some(2));
+/// assert_eq!(partial.patch, None);
+/// assert_eq!(partial.to_string(), "1.2");
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PartialVersion {
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub pre: Prerelease,
+    pub build: BuildMetadata,
+}
+
 /// **SemVer version requirement** describing the intersection of some version
-/// comparators, such as `>=1.2.3, <1.8`.
+/// comparators, such as `>=1.2.3 <1.8`.
 ///
 /// # Syntax
 ///
-/// - Either `*` (meaning "any"), or one or more comma-separated comparators.
+/// - Either `*` (meaning "any"), or one or more whitespace-separated
+///   comparators. Commas are not separators: `>=1.2.3, <1.8` is an error.
+///   An empty string and `latest`, in any case, are accepted as aliases of
+///   `*`. No dist-tag lookup is involved: `latest` matches every release.
 ///
 /// - A [`Comparator`] is an operator ([`Op`]) and a partial version, separated
 ///   by optional whitespace. For example `>=1.0.0` or `>=1.0`.
@@ -181,7 +309,7 @@ pub struct Version {
 ///   completely ignored, as it's never relevant to whether any comparator
 ///   matches a particular version.
 ///
-/// - Whitespace is permitted around commas and around operators. Whitespace is
+/// - Whitespace is permitted around operators. Whitespace is
 ///   not permitted within a partial version, i.e. anywhere between the major
 ///   version number and its minor, patch, pre-release, or build metadata.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -251,10 +379,27 @@ pub struct Comparator {
 /// - &ensp;**`^I.J`**&ensp;(for I\>0 or J\>0)&emsp;&mdash;&emsp;equivalent to `^I.J.0`
 /// - &ensp;**`^0.0`**&emsp;&mdash;&emsp;equivalent to `=0.0`
 /// - &ensp;**`^I`**&emsp;&mdash;&emsp;equivalent to `=I`
+/// - &ensp;**`^I.J.*`**&emsp;&mdash;&emsp;equivalent to `^I.J`
+/// - &ensp;**`^I.*`**&ensp;or&ensp;**`^I.*.*`**&emsp;&mdash;&emsp;equivalent to `^I`
+///
+/// A caret requirement needs at least a major version number, so `^`, `^*`
+/// and `^x` are errors rather than another spelling of `*`.
+///
+/// # Op::Pessimistic&emsp;(as in Bundler)
+/// *Pessimistic requirements allow the **last** part written to increase, and
+/// anything after it.*
+/// - &ensp;**`~>I.J.K`**&emsp;&mdash;&emsp;equivalent to `~I.J.K`
+/// - &ensp;**`~>I.J`**&emsp;&mdash;&emsp;equivalent to `>=I.J.0, <(I+1).0.0`
+/// - &ensp;**`~>I`**&emsp;&mdash;&emsp;equivalent to `=I`
 ///
 /// # Op::Wildcard
 /// - &ensp;**`I.J.*`**&emsp;&mdash;&emsp;equivalent to `=I.J`
 /// - &ensp;**`I.*`**&ensp;or&ensp;**`I.*.*`**&emsp;&mdash;&emsp;equivalent to `=I`
+///
+/// After any other operator, a wildcard is the same as leaving out the
+/// components it stands for, so `>=I.*` is `>=I`, that is `>=I.0.0`, and
+/// `<=I.J.*` is `<=I.J`, that is `<I.(J+1).0`. A wildcard major version as in
+/// `>=*` is an error, since it is only meaningful as the whole requirement.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[cfg_attr(not(no_non_exhaustive), non_exhaustive)]
 pub enum Op {
@@ -266,6 +411,75 @@ pub enum Op {
     Tilde,
     Caret,
     Wildcard,
+    Pessimistic,
+
+    #[cfg(no_non_exhaustive)] // rustc <1.40
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+/// Kind of a token reported by [`VersionReq::parse_verbose`].
+///
+/// Token spans do not include whitespace or the punctuation between the
+/// parts of a partial version: in `>=1.2.3-rc+b` there is an `Operator` token
+/// for `>=`, then `Major`, `Minor`, `Patch`, `Pre`, and `Build` tokens for
+/// `1`, `2`, `3`, `rc`, and `b`. A wildcard such as the `x` in `1.x` is
+/// reported as the component it stands in for, and `latest`, an alias of `*`,
+/// as a `Major` token.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(not(no_non_exhaustive), non_exhaustive)]
+pub enum TokenKind {
+    Operator,
+    Major,
+    Minor,
+    Patch,
+    Pre,
+    Build,
+    Hyphen,
+    Or,
+
+    #[cfg(no_non_exhaustive)] // rustc <1.40
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+/// How [`VersionReq::matches_with`] treats the exclusive upper bound of a
+/// comparator, such as the `1.3.0` that ends `~1.2.3`.
+///
+/// The default is `Exclusive`, the same as [`VersionReq::matches`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(not(no_non_exhaustive), non_exhaustive)]
+pub enum IncludeBoundsMode {
+    /// A version equal to an exclusive upper bound does not match: `~1.2.3`
+    /// does not match `1.3.0`, and `<2.0.0` does not match `2.0.0`.
+    Exclusive,
+    /// Exclusive upper bounds are treated as inclusive: `~1.2.3` matches
+    /// `1.3.0`, and `<2.0.0` matches `2.0.0`. Versions above the bound, and
+    /// pre-releases of it, still do not match `~1.2.3`.
+    Inclusive,
+
+    #[cfg(no_non_exhaustive)] // rustc <1.40
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+/// The outcome of [`VersionReq::matches_with_reason`].
+///
+/// Matching happens in two phases. First the version must lie within one of
+/// the requirement's `||` alternatives. Then, if it is a pre-release, some
+/// comparator of the requirement must allow pre-releases of its
+/// major.minor.patch, as described for [`Prerelease`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(not(no_non_exhaustive), non_exhaustive)]
+pub enum MatchReason {
+    /// The version matches.
+    Matched,
+    /// The version is not within any of the requirement's ranges.
+    NoRangeMatched,
+    /// The version is a pre-release within one of the requirement's ranges,
+    /// but no comparator allows pre-releases of its major.minor.patch, so
+    /// `>=1.0.0` does not match `1.5.0-rc.1`.
+    PrereleaseRejected,
 
     #[cfg(no_non_exhaustive)] // rustc <1.40
     #[doc(hidden)]
@@ -438,6 +652,736 @@ impl Version {
     pub fn parse(text: &str) -> Result<Self, Error> {
         Version::from_str(text)
     }
+
+    /// Parse a `Version`, reporting a separate error for each of the
+    /// major.minor.patch, pre-release, and build metadata that is malformed
+    /// rather than only the first.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let errors = Version::parse_collect_errors("1.2.3-01+.").unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].0, 6);
+    /// assert_eq!(errors[1].0, 9);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`parse`][Version::parse], with each error paired with
+    /// the byte offset in `text` of the start of its part.
+    pub fn parse_collect_errors(text: &str) -> Result<Self, Vec<(usize, Error)>> {
+        parse::version_collect_errors(text)
+    }
+
+    /// Create `Version` by parsing from bytes, such as from a network buffer,
+    /// without first checking that the whole of `bytes` is UTF-8.
+    ///
+    /// A version consists of ASCII characters only, so a byte outside of
+    /// ASCII is an error, which is reported for the part of the version it is
+    /// in, as in "unexpected character in pre-release identifier".
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let version = Version::parse_bytes(b"1.2.3-rc.1").unwrap();
+    /// assert_eq!(version, Version::parse("1.2.3-rc.1").unwrap());
+    ///
+    /// let err = Version::parse_bytes(b"1.2.3-\xff").unwrap_err();
+    /// assert_eq!(err.to_string(), "unexpected character in pre-release identifier");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`Version::parse`], as well as for non-ASCII bytes.
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        parse::version_from_bytes(bytes)
+    }
+
+    /// Create `Version` by parsing from string representation, ignoring any
+    /// leading or trailing ASCII whitespace such as the newline at the end of
+    /// a file. Whitespace within the version is still an error.
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`Version::parse`], applied to the trimmed text.
+    pub fn parse_trimmed(text: &str) -> Result<Self, Error> {
+        Version::from_str(text.trim_matches(|ch: char| ch.is_ascii_whitespace()))
+    }
+
+    /// Parse a version from the start of `text`, returning it along with the
+    /// rest of the text after it, for versions embedded in larger strings such
+    /// as `1.2.3 (2023-01-01)`.
+    ///
+    /// Parsing is greedy: the version extends over the longest prefix that
+    /// could be part of one. In particular a `-` or `+` after the patch number
+    /// always starts a pre-release or build metadata, which take in every
+    /// following letter, digit, hyphen, and dot-separated identifier, so for
+    /// `1.2.3-linux-x64` the pre-release is `linux-x64` and nothing is left
+    /// over. Use a delimiter that cannot be part of a version, such as a space
+    /// or `/`, to separate the version from what follows.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let (version, rest) = Version::parse_prefix("1.2.3 (2023-01-01)").unwrap();
+    /// assert_eq!(version, Version::new(1, 2, 3));
+    /// assert_eq!(rest, " (2023-01-01)");
+    ///
+    /// let (version, rest) = Version::parse_prefix("1.2.3-linux-x64").unwrap();
+    /// assert_eq!(version.pre.as_str(), "linux-x64");
+    /// assert_eq!(rest, "");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`Version::parse`], for the part of `text` that is
+    /// parsed, except that trailing characters are not an error. A `-` or `+`
+    /// that is not followed by a valid identifier is an error, as in `1.2.3-`
+    /// or `1.2.3-rc..1`.
+    pub fn parse_prefix(text: &str) -> Result<(Self, &str), Error> {
+        let (version, _pos, rest) = parse::version_prefix(text)?;
+        Ok((version, rest))
+    }
+
+    /// Parse the output of `git describe --tags` for a repository whose tags
+    /// are versions, such as `1.2.3` for a tagged commit or `1.2.3-4-gabc1234`
+    /// for the commit `abc1234`, 4 commits after the tag `1.2.3`.
+    ///
+    /// A trailing `-N-gHASH`, with `N` a number and `HASH` hexadecimal, is
+    /// taken to be added by `git describe` and returned separately, rather
+    /// than as part of a pre-release. The tag before it is parsed as with
+    /// [`Version::parse`], and may have a pre-release of its own, as in
+    /// `1.2.3-rc.1-4-gabc1234`. Without such a suffix, the whole text is parsed
+    /// as the tag.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let (version, describe) = Version::parse_git_describe("1.2.3-4-gabc1234").unwrap();
+    /// assert_eq!(version, Version::new(1, 2, 3));
+    /// let describe = describe.unwrap();
+    /// assert_eq!(describe.commits_ahead, 4);
+    /// assert_eq!(describe.sha, "abc1234");
+    ///
+    /// let (version, describe) = Version::parse_git_describe("1.2.3").unwrap();
+    /// assert_eq!(version, Version::new(1, 2, 3));
+    /// assert_eq!(describe, None);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`Version::parse`], for the tag. A `v` prefix, as in
+    /// `v1.2.3`, is not accepted. Output of `git describe --dirty` that ends
+    /// in `-dirty` has no suffix of the form above, so it parses as a
+    /// pre-release.
+    pub fn parse_git_describe(text: &str) -> Result<(Self, Option<GitDescribe>), Error> {
+        parse::version_git_describe(text)
+    }
+
+    /// Create a version by parsing text in which the major, minor, and patch
+    /// numbers may have leading zeros, such as `01.02.03`, which is not valid
+    /// SemVer and is rejected by [`Version::parse`].
+    ///
+    /// The result keeps the original text for display alongside the numeric
+    /// version, which is available from [`PaddedVersion::version`].
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`Version::parse`], except that leading zeros are
+    /// allowed in the major, minor, and patch numbers. They are still an error
+    /// in numeric pre-release identifiers, as in `1.0.0-rc.01`.
+    pub fn parse_preserve_zeros(text: &str) -> Result<PaddedVersion, Error> {
+        parse::version_preserve_zeros(text)
+    }
+
+    /// A byte string encoding of this version's precedence, for use as a key
+    /// in an ordered key-value store. Comparing the keys of two versions as
+    /// byte strings gives the same result as comparing the versions by
+    /// precedence, with a release sorting after its pre-releases.
+    ///
+    /// Build metadata is not part of the key, so versions that differ only in
+    /// build metadata have the same key.
+    pub fn to_sortable_key(&self) -> Vec<u8> {
+        key::encode(self)
+    }
+
+    /// Decode a key produced by [`to_sortable_key`][Version::to_sortable_key],
+    /// giving a version without build metadata. Returns `None` if `bytes` is
+    /// not such a key.
+    pub fn from_sortable_key(bytes: &[u8]) -> Option<Version> {
+        key::decode(bytes)
+    }
+
+    /// The release that this version leads up to, which is `self` without its
+    /// pre-release and build metadata: `1.2.3` for `1.2.3-rc.1`. A release
+    /// without build metadata is returned unchanged.
+    pub fn release_version(&self) -> Version {
+        Version::new(self.major, self.minor, self.patch)
+    }
+
+    /// The major, minor, and patch numbers as a tuple, dropping any
+    /// pre-release and build metadata. The inverse of the `From<(u64, u64,
+    /// u64)>` impl for release versions.
+    pub fn as_triple(&self) -> (u64, u64, u64) {
+        (self.major, self.minor, self.patch)
+    }
+
+    /// Create a release version from `[major, minor, patch]`. Like
+    /// [`Version::new`], this can be used in constants:
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// const SUPPORTED: &[Version] = &[
+    ///     Version::from_array([1, 0, 0]),
+    ///     Version::from_array([1, 4, 2]),
+    /// ];
+    /// ```
+    pub const fn from_array(array: [u64; 3]) -> Self {
+        Version::new(array[0], array[1], array[2])
+    }
+
+    /// The major, minor, and patch numbers as an array, dropping any
+    /// pre-release and build metadata.
+    pub const fn to_array(&self) -> [u64; 3] {
+        [self.major, self.minor, self.patch]
+    }
+
+    /// The greatest version of lower precedence than `self`, if there is one,
+    /// so that `<self` matches the same versions as `<=predecessor`.
+    ///
+    /// Most versions have no such neighbor. Below a release `1.2.3` there are
+    /// its pre-releases, and for any of them, such as `1.2.3-rc.1`, there is
+    /// a greater one, such as `1.2.3-rc.1.0` or `1.2.3-rc.2`, that is still
+    /// less than `1.2.3`. The same goes for most pre-releases. A predecessor
+    /// exists in only two cases:
+    ///
+    /// - &ensp;**`I.J.K-0`**, the lowest pre-release of
+    ///   `I.J.K`&emsp;&mdash;&emsp;the release before it, `I.J.(K-1)`, where a
+    ///   component that would go below 0 is instead 0 and the component before
+    ///   it is decremented with the later ones at u64::MAX, as in `1.4.0-0`
+    ///   &rarr; <code>1.3.18446744073709551615</code>. `0.0.0-0` is the lowest
+    ///   version of all and has no predecessor.
+    /// - &ensp;**`I.J.K-pre.0`**&emsp;&mdash;&emsp;`I.J.K-pre`, since no
+    ///   pre-release falls between the two.
+    ///
+    /// Otherwise the result is `None`. Build metadata on `self` is ignored,
+    /// and the result never has any.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let predecessor = |text| Version::parse(text).unwrap().predecessor();
+    /// assert_eq!(predecessor("1.2.3-0"), Some(Version::new(1, 2, 2)));
+    /// assert_eq!(predecessor("1.2.3-rc.1.0"), Some(Version::parse("1.2.3-rc.1").unwrap()));
+    /// assert_eq!(predecessor("1.2.3"), None);
+    /// assert_eq!(predecessor("1.2.3-rc.1"), None);
+    /// ```
+    pub fn predecessor(&self) -> Option<Version> {
+        eval::predecessor(self)
+    }
+
+    /// The lowest version with the same major, minor, and patch version as
+    /// `self`, which is the `-0` pre-release: `1.2.3-0` for `1.2.3`.
+    ///
+    /// It is below `1.2.3` and all of its other pre-releases, and above
+    /// `1.2.2` and everything else of lower precedence, so `<1.2.3-0` matches
+    /// only versions below every pre-release of `1.2.3`. Any pre-release and
+    /// build metadata of `self` are replaced.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let min = Version::new(1, 2, 3).with_min_prerelease();
+    /// assert_eq!(min.to_string(), "1.2.3-0");
+    /// assert!(min < Version::new(1, 2, 3));
+    /// assert!(min > Version::new(1, 2, 2));
+    /// ```
+    pub fn with_min_prerelease(&self) -> Version {
+        Version {
+            pre: Prerelease {
+                identifier: unsafe { Identifier::new_unchecked("0") },
+            },
+            ..Version::new(self.major, self.minor, self.patch)
+        }
+    }
+
+    /// The lowest version that is not SemVer compatible with `self`, which is
+    /// the exclusive upper bound of the caret requirement `^self`.
+    ///
+    /// - &ensp;**`I.J.K`**&ensp;(for I\>0)&emsp;&mdash;&emsp;`(I+1).0.0`
+    /// - &ensp;**`0.J.K`**&ensp;(for J\>0)&emsp;&mdash;&emsp;`0.(J+1).0`
+    /// - &ensp;**`0.0.K`**&emsp;&mdash;&emsp;`0.0.(K+1)`
+    ///
+    /// The result never has a pre-release or build metadata.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the incremented component would exceed u64::MAX, in release
+    /// builds as well as debug builds.
+    pub fn next_breaking(&self) -> Version {
+        let increment = |n: u64| n.checked_add(1).expect("version number overflow");
+        if self.major > 0 {
+            Version::new(increment(self.major), 0, 0)
+        } else if self.minor > 0 {
+            Version::new(0, increment(self.minor), 0)
+        } else {
+            Version::new(0, 0, increment(self.patch))
+        }
+    }
+
+    /// The next major version, `(I+1).0.0` for `I.J.K`, without any
+    /// pre-release or build metadata.
+    ///
+    /// If the major version is already u64::MAX, the result is `self`'s
+    /// major.minor.patch unchanged, so that a bump never goes backwards. Use
+    /// [`checked_bump_major`][Version::checked_bump_major] to detect this
+    /// case.
+    pub fn bump_major(&self) -> Version {
+        match self.major.checked_add(1) {
+            Some(major) => Version::new(major, 0, 0),
+            None => Version::new(self.major, self.minor, self.patch),
+        }
+    }
+
+    /// The next minor version, `I.(J+1).0` for `I.J.K`, without any
+    /// pre-release or build metadata.
+    ///
+    /// If the minor version is already u64::MAX, the result is `self`'s
+    /// major.minor.patch unchanged, so that a bump never goes backwards. Use
+    /// [`checked_bump_minor`][Version::checked_bump_minor] to detect this
+    /// case.
+    pub fn bump_minor(&self) -> Version {
+        match self.minor.checked_add(1) {
+            Some(minor) => Version::new(self.major, minor, 0),
+            None => Version::new(self.major, self.minor, self.patch),
+        }
+    }
+
+    /// The next patch version, `I.J.(K+1)` for `I.J.K`, without any
+    /// pre-release or build metadata.
+    ///
+    /// If the patch version is already u64::MAX, it stays at u64::MAX. Use
+    /// [`checked_bump_patch`][Version::checked_bump_patch] to detect this
+    /// case.
+    pub fn bump_patch(&self) -> Version {
+        Version::new(self.major, self.minor, self.patch.saturating_add(1))
+    }
+
+    /// Like [`bump_major`][Version::bump_major], but `None` if the major
+    /// version is already u64::MAX.
+    pub fn checked_bump_major(&self) -> Option<Version> {
+        let major = self.major.checked_add(1)?;
+        Some(Version::new(major, 0, 0))
+    }
+
+    /// Like [`bump_minor`][Version::bump_minor], but `None` if the minor
+    /// version is already u64::MAX.
+    pub fn checked_bump_minor(&self) -> Option<Version> {
+        let minor = self.minor.checked_add(1)?;
+        Some(Version::new(self.major, minor, 0))
+    }
+
+    /// Like [`bump_patch`][Version::bump_patch], but `None` if the patch
+    /// version is already u64::MAX.
+    pub fn checked_bump_patch(&self) -> Option<Version> {
+        let patch = self.patch.checked_add(1)?;
+        Some(Version::new(self.major, self.minor, patch))
+    }
+
+    /// The versions from `from` up to but not including `to`, counting up by
+    /// patch version: `from` itself, then each release after it that is less
+    /// than `to`.
+    ///
+    /// Nothing is yielded unless `from` is less than `to` and they share their
+    /// major and minor versions. Versions are compared by precedence, ignoring
+    /// build metadata.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let from = Version::new(1, 2, 0);
+    /// let to = Version::new(1, 2, 3);
+    /// let versions = Version::patch_range(&from, &to)
+    ///     .map(|v| v.to_string())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(versions, ["1.2.0", "1.2.1", "1.2.2"]);
+    ///
+    /// assert_eq!(Version::patch_range(&from, &Version::new(1, 3, 0)).count(), 0);
+    /// ```
+    pub fn patch_range(from: &Version, to: &Version) -> impl Iterator<Item = Version> {
+        let (major, minor) = (from.major, from.minor);
+        let (first, patches) = if major == to.major
+            && minor == to.minor
+            && eval::cmp_precedence(from, to) == Ordering::Less
+        {
+            // A pre-release is followed by its own release. A release can't
+            // have patch u64::MAX here, as `to` would have to be greater.
+            let start = if from.pre.is_empty() {
+                from.patch + 1
+            } else {
+                from.patch
+            };
+            (Some(from.clone()), start..to.patch)
+        } else {
+            (None, 0..0)
+        };
+        first
+            .into_iter()
+            .chain(patches.map(move |patch| Version::new(major, minor, patch)))
+    }
+
+    /// The requirement `~self`, which matches versions from `self` up to the
+    /// next minor version. For `1.2.3` this is `~1.2.3`, i.e. `>=1.2.3 <1.3.0`.
+    ///
+    /// Any pre-release of `self` is kept in the comparator, so that `self`
+    /// always matches the requirement. Build metadata is left out.
+    pub fn tilde_req(&self) -> VersionReq {
+        self.single_req(Op::Tilde)
+    }
+
+    /// The requirement `^self`, which matches versions from `self` up to
+    /// [`next_breaking`][Version::next_breaking]. For `1.2.3` this is
+    /// `^1.2.3`, i.e. `>=1.2.3 <2.0.0`.
+    ///
+    /// Any pre-release of `self` is kept in the comparator, so that `self`
+    /// always matches the requirement. Build metadata is left out.
+    pub fn caret_req(&self) -> VersionReq {
+        self.single_req(Op::Caret)
+    }
+
+    /// The requirement matching every release with the same major version
+    /// as `self`, starting from its `.0.0` rather than from `self`. For
+    /// `1.2.3` this is `>=1.0.0 <2.0.0`, which unlike `^1.2.3` also matches
+    /// `1.0.0`.
+    ///
+    /// Any pre-release and build metadata of `self` are ignored, so the
+    /// requirement matches releases only, like `1.*`. If the major version is
+    /// `u64::MAX`, there is no upper bound.
+    pub fn same_major_req(&self) -> VersionReq {
+        let lower = Version::new(self.major, 0, 0);
+        let upper = match self.major.checked_add(1) {
+            Some(major) => Some(Version::new(major, 0, 0)),
+            None => None,
+        };
+        lower.range_req(upper)
+    }
+
+    /// The requirement matching every release with the same major and minor
+    /// version as `self`, starting from its `.0` rather than from `self`. For
+    /// `1.2.3` this is `>=1.2.0 <1.3.0`, which unlike `~1.2.3` also matches
+    /// `1.2.0`.
+    ///
+    /// As with [`same_major_req`][Version::same_major_req], the requirement
+    /// matches releases only, like `1.2.*`. If the minor version is
+    /// `u64::MAX`, there is no upper bound.
+    pub fn same_minor_req(&self) -> VersionReq {
+        let lower = Version::new(self.major, self.minor, 0);
+        let upper = match self.minor.checked_add(1) {
+            Some(minor) => Some(Version::new(self.major, minor, 0)),
+            None => None,
+        };
+        lower.range_req(upper)
+    }
+
+    /// The hyphen range of releases within `tolerance` patch versions of
+    /// `self` either way, with the same major and minor version. For `1.2.3`
+    /// and a tolerance of 2 this is `1.2.1 - 1.2.5`.
+    ///
+    /// The bounds saturate at patch versions 0 and u64::MAX, so for `1.2.1`
+    /// and a tolerance of 2 the range is `1.2.0 - 1.2.3`. A tolerance of 0
+    /// gives a range matching only the release of `self`. The pre-release and
+    /// build metadata of `self` are left out.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let req = Version::new(1, 2, 3).patch_window_req(2);
+    /// assert_eq!(req.to_string(), "=1.2.1 - =1.2.5");
+    /// ```
+    pub fn patch_window_req(&self, tolerance: u64) -> VersionReq {
+        let lower = Version::new(self.major, self.minor, self.patch.saturating_sub(tolerance));
+        let upper = Version::new(self.major, self.minor, self.patch.saturating_add(tolerance));
+        lower.hyphen_req(&upper)
+    }
+
+    /// The tightest requirement with a single operator that matches both `a`
+    /// and `b`, for suggesting a requirement that covers two versions:
+    ///
+    /// - a tilde requirement on the lower version if they have the same major
+    ///   and minor version, such as `~1.2.3` for `1.2.3` and `1.2.7`;
+    /// - otherwise a caret requirement on the lower version if they have the
+    ///   same major version other than 0, such as `^1.2.3` for `1.2.3` and
+    ///   `1.5.0`;
+    /// - otherwise a hyphen range from the lower to the higher version, such
+    ///   as `1.2.3 - 2.0.0`.
+    ///
+    /// A hyphen range is also used when the higher version is a pre-release
+    /// that the tilde or caret requirement would not match.
+    pub fn loosest_req_including(a: &Version, b: &Version) -> VersionReq {
+        let (lo, hi) = if eval::cmp_precedence(a, b) == Ordering::Greater {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let req = if lo.major == hi.major && lo.minor == hi.minor {
+            lo.tilde_req()
+        } else if lo.major == hi.major && lo.major > 0 {
+            lo.caret_req()
+        } else {
+            lo.hyphen_req(hi)
+        };
+        if req.matches(hi) {
+            req
+        } else {
+            lo.hyphen_req(hi)
+        }
+    }
+
+    // `>=self <upper`, or `>=self` without an upper bound.
+    fn range_req(&self, upper: Option<Version>) -> VersionReq {
+        let range = match upper {
+            Some(upper) => VersionRange::Intersection(vec![
+                self.comparator(Op::GreaterEq),
+                upper.comparator(Op::Less),
+            ]),
+            None => VersionRange::Simple(self.comparator(Op::GreaterEq)),
+        };
+        VersionReq {
+            ranges: vec![range],
+        }
+    }
+
+    fn single_req(&self, op: Op) -> VersionReq {
+        VersionReq {
+            ranges: vec![VersionRange::Simple(self.comparator(op))],
+        }
+    }
+
+    fn hyphen_req(&self, upper: &Version) -> VersionReq {
+        let range = VersionRange::Hyphen(self.comparator(Op::Exact), upper.comparator(Op::Exact));
+        VersionReq {
+            ranges: vec![range],
+        }
+    }
+
+    fn comparator(&self, op: Op) -> Comparator {
+        Comparator {
+            op,
+            major: self.major,
+            minor: Some(self.minor),
+            patch: Some(self.patch),
+            pre: self.pre.clone(),
+        }
+    }
+
+    /// The next pre-release in the same series as this one, with build
+    /// metadata removed.
+    ///
+    /// - `1.0.0-rc.1` &mdash; `1.0.0-rc.2`
+    /// - `1.0.0-rc` &mdash; `1.0.0-rc.1`
+    /// - `1.0.0-alpha.beta` &mdash; `1.0.0-alpha.beta.1`
+    ///
+    /// Returns `None` if this version has no pre-release.
+    pub fn with_pre_incremented(&self) -> Option<Version> {
+        if self.pre.is_empty() {
+            return None;
+        }
+        let pre = self.pre.as_str();
+        let last = pre.rsplit('.').next().unwrap();
+        let mut string = String::with_capacity(pre.len() + 2);
+        if last.bytes().all(|b| b.is_ascii_digit()) {
+            // Increment the decimal digits in place, so that numeric
+            // identifiers wider than u64 are handled too.
+            let mut digits = last.as_bytes().to_vec();
+            let mut i = digits.len();
+            loop {
+                if i == 0 {
+                    digits.insert(0, b'1');
+                    break;
+                }
+                i -= 1;
+                if digits[i] == b'9' {
+                    digits[i] = b'0';
+                } else {
+                    digits[i] += 1;
+                    break;
+                }
+            }
+            string.push_str(&pre[..pre.len() - last.len()]);
+            string.push_str(core::str::from_utf8(&digits).unwrap());
+        } else {
+            string.push_str(pre);
+            string.push_str(".1");
+        }
+        Some(Version {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            pre: Prerelease {
+                identifier: unsafe { Identifier::new_unchecked(&string) },
+            },
+            build: BuildMetadata::EMPTY,
+        })
+    }
+
+    /// The major, minor, and patch numbers joined by `sep`, for example
+    /// `1_2_3` for version `1.2.3-rc.1` with `sep` of `'_'`. Pre-release and
+    /// build metadata are left out.
+    pub fn format_with(&self, sep: char) -> String {
+        let mut string = String::new();
+        let _ = write!(
+            string,
+            "{}{}{}{}{}",
+            self.major, sep, self.minor, sep, self.patch,
+        );
+        string
+    }
+
+    /// The version matched by `req` that is nearest to `self`, for correcting
+    /// a pin that has fallen outside of a requirement.
+    ///
+    /// - If `self` matches, it is returned unchanged.
+    /// - If `self` is below one of the ranges of `req`, the result is the
+    ///   lowest release in the next range above `self`. For example `1.0.0`
+    ///   clamped to `^1.2.3` is `1.2.3`, and clamped to `>1.2.3` is `1.2.4`.
+    /// - If `self` is above every range of `req`, the result is the inclusive
+    ///   upper bound of the last range, such as `1.4.0` for `<=1.4.0`. Most
+    ///   ranges have an exclusive upper bound, below which there is no
+    ///   greatest version, so in that case the range's lower bound is used
+    ///   instead: `3.0.0` clamped to `^1.2.3` is `1.2.3`.
+    /// - A pre-release of `self` that is within a range of `req` but not
+    ///   matched by it is rounded up to its release, so `1.5.0-rc.1` clamped
+    ///   to `^1.2.3` is `1.5.0`.
+    ///
+    /// Returns `None` if `req` matches no release at all, such as `<0.0.0`.
+    pub fn clamp_to_req(&self, req: &VersionReq) -> Option<Version> {
+        if req.matches(self) {
+            return Some(self.clone());
+        }
+        interval::clamp(req, self)
+    }
+
+    /// The highest of `available` that matches `req` and is newer than
+    /// `current`, or `None` if there is no such update.
+    ///
+    /// Newer means greater in precedence, so a version differing from
+    /// `current` only in build metadata is not an update. Pre-releases are
+    /// candidates only as far as `req` matches them, as usual: `^1.2.3` never
+    /// selects `1.5.0-rc.1`, while `>=1.2.3-rc.1` may select `1.2.3-rc.2`.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    ///
+    /// let available = ["1.2.3", "1.4.0", "1.9.0-rc.1", "2.0.0"]
+    ///     .iter()
+    ///     .map(|v| Version::parse(v).unwrap())
+    ///     .collect::<Vec<_>>();
+    /// let req = VersionReq::parse("^1.2").unwrap();
+    ///
+    /// let current = Version::new(1, 2, 3);
+    /// let update = Version::select_update(&current, &req, &available);
+    /// assert_eq!(update, Some(&Version::new(1, 4, 0)));
+    ///
+    /// let current = Version::new(1, 4, 0);
+    /// assert_eq!(Version::select_update(&current, &req, &available), None);
+    /// ```
+    pub fn select_update<'a>(
+        current: &Version,
+        req: &VersionReq,
+        available: &'a [Version],
+    ) -> Option<&'a Version> {
+        available
+            .iter()
+            .filter(|version| eval::cmp_precedence(version, current) == Ordering::Greater)
+            .filter(|version| req.matches(version))
+            .max()
+    }
+
+    /// Whether `self` and `other` have the same major, minor, and patch
+    /// version, regardless of pre-release and build metadata. For example
+    /// `1.2.3-rc.1` and `1.2.3` are the same release, but they are not equal
+    /// in precedence.
+    pub fn same_release(&self, other: &Version) -> bool {
+        self.major == other.major && self.minor == other.minor && self.patch == other.patch
+    }
+
+    /// Compare by SemVer precedence, then break ties between versions that
+    /// differ only in build metadata by comparing the build metadata strings
+    /// lexicographically, so `1.0.0+a` sorts before `1.0.0+b`.
+    ///
+    /// This is a total order, like [`Ord`], but it is simpler to reproduce
+    /// with other tools than `Ord`'s identifier-by-identifier comparison of
+    /// build metadata: for example `1.0.0+10` is less than `1.0.0+9` here,
+    /// while under `Ord` it is greater.
+    pub fn cmp_with_build(&self, other: &Version) -> Ordering {
+        eval::cmp_precedence(self, other)
+            .then_with(|| self.build.as_str().cmp(other.build.as_str()))
+    }
+
+    /// A measure of how far apart two versions are, for ranking candidates by
+    /// closeness to a target version. Smaller is closer, and the distance from
+    /// a version to itself is 0.
+    ///
+    /// The distance is `major_diff * 10^12 + minor_diff * 10^6 + patch_diff`,
+    /// the weighted sum of the absolute differences of each number, so any
+    /// difference in major version outweighs all differences in minor and
+    /// patch version, and likewise for minor and patch. To keep it that way,
+    /// differences in minor and patch version saturate at 999999.
+    /// Pre-release and build metadata are not taken into account.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let target = Version::new(1, 2, 3);
+    /// assert_eq!(target.distance(&Version::new(1, 2, 5)), 2);
+    /// assert_eq!(target.distance(&Version::new(1, 4, 0)), 2_000_003);
+    /// assert!(target.distance(&Version::new(1, 9, 0)) < target.distance(&Version::new(2, 2, 3)));
+    /// ```
+    pub fn distance(&self, other: &Version) -> u128 {
+        const SATURATE: u64 = 999_999;
+        let diff = |a: u64, b: u64| if a > b { a - b } else { b - a };
+        let major = diff(self.major, other.major) as u128;
+        let minor = diff(self.minor, other.minor).min(SATURATE) as u128;
+        let patch = diff(self.patch, other.patch).min(SATURATE) as u128;
+        major * 1_000_000_000_000 + minor * 1_000_000 + patch
+    }
+
+    /// Whether this version matches every one of the requirements. This is
+    /// true for an empty list of requirements.
+    pub fn satisfies_all(&self, reqs: &[VersionReq]) -> bool {
+        reqs.iter().all(|req| req.matches(self))
+    }
+
+    /// Whether this version matches at least one of the requirements. This is
+    /// false for an empty list of requirements.
+    pub fn satisfies_any(&self, reqs: &[VersionReq]) -> bool {
+        reqs.iter().any(|req| req.matches(self))
+    }
+
+    /// Whether this version is within the range implied by a partial version
+    /// `tag`, the same as matching `=tag`: `"1"` matches every `1.x.y`
+    /// release and `"1.2"` matches every `1.2.y` release.
+    ///
+    /// A pre-release version matches only a tag that spells out the same
+    /// pre-release, such as `"1.2.3-rc.1"`. Build metadata is ignored on both
+    /// sides. A tag that is not a valid partial version matches nothing.
+    ///
+    /// ```
+    /// use semver::Version;
+    ///
+    /// let version = Version::parse("1.2.3").unwrap();
+    /// assert!(version.matches_tag("1"));
+    /// assert!(version.matches_tag("1.2"));
+    /// assert!(!version.matches_tag("1.3"));
+    /// ```
+    pub fn matches_tag(&self, tag: &str) -> bool {
+        match PartialVersion::from_str(tag) {
+            Ok(tag) => eval::matches_tag(&tag, self),
+            Err(_) => false,
+        }
+    }
 }
 
 impl VersionReq {
@@ -468,20 +1412,724 @@ impl VersionReq {
     ///
     /// - `@1.0.0` &mdash; unrecognized comparison operator.
     ///
-    /// - `^1.0.0, ` &mdash; unexpected end of input.
-    ///
-    /// - `>=1.0 <2.0` &mdash; missing comma between comparators.
+    /// - `>=1.0, <2.0` &mdash; comma between comparators, which are separated
+    ///   by whitespace only.
     ///
     /// - `*.*` &mdash; unsupported wildcard syntax.
+    ///
+    /// - `>1.0.0 - 2.0.0` &mdash; comparison operator on an endpoint of a
+    ///   hyphen range.
     pub fn parse(text: &str) -> Result<Self, Error> {
         VersionReq::from_str(text)
     }
 
+    /// Create `VersionReq` from its `||` alternatives.
+    ///
+    /// ```
+    /// use semver::{Comparator, VersionRange, VersionReq};
+    ///
+    /// let req = VersionReq::from_ranges(vec![
+    ///     VersionRange::Simple(Comparator::parse("^1.2").unwrap()),
+    ///     VersionRange::Hyphen(
+    ///         Comparator::parse("3.0.0").unwrap(),
+    ///         Comparator::parse("3.4").unwrap(),
+    ///     ),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(req.to_string(), "^1.2 || =3.0.0 - =3.4");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if any hyphen range has its endpoints the wrong way around, so
+    /// that it could not match any version, such as `2.0.0 - 1.0.0`, or if an
+    /// endpoint has an operator other than [`Op::Exact`] or [`Op::Wildcard`].
+    pub fn from_ranges(ranges: Vec<VersionRange>) -> Result<Self, Error> {
+        parse::version_req_from_ranges(ranges)
+    }
+
+    /// Parse a `VersionReq`, reporting a separate error for each of its `||`
+    /// alternatives that is malformed rather than only the first.
+    ///
+    /// ```
+    /// use semver::VersionReq;
+    ///
+    /// let errors = VersionReq::parse_collect_errors("^1.q || ~2 || >=3.0.y").unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].0, 0);
+    /// assert_eq!(errors[1].0, 14);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`parse`][VersionReq::parse], with each error paired with
+    /// the byte offset in `text` of the start of its alternative. Only the
+    /// first alternative may be `*`, `latest`, or empty. A problem that does
+    /// not belong to any one alternative, such as `*` being followed by
+    /// another alternative, is reported as a single error at offset 0.
+    pub fn parse_collect_errors(text: &str) -> Result<Self, Vec<(usize, Error)>> {
+        parse::version_req_collect_errors(text)
+    }
+
+    /// Parse a `VersionReq` that is not allowed to mention pre-releases, for
+    /// registries whose policy is to reject requirements like `^1.2.3-rc.1`.
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors of [`parse`][VersionReq::parse], fails if any
+    /// comparator has a pre-release, with an error that gives the byte offset
+    /// in `text` of the first pre-release's `-`.
+    pub fn parse_no_prerelease(text: &str) -> Result<Self, Error> {
+        parse::version_req_no_prerelease(text)
+    }
+
+    /// Parse a `VersionReq` and also return the byte range and kind of each of
+    /// its tokens, in order, for example for syntax highlighting.
+    ///
+    /// ```
+    /// use semver::{TokenKind, VersionReq};
+    ///
+    /// let (req, tokens) = VersionReq::parse_verbose("^1.2").unwrap();
+    /// assert_eq!(req, VersionReq::parse("^1.2").unwrap());
+    /// assert_eq!(
+    ///     tokens,
+    ///     [
+    ///         (0..1, TokenKind::Operator),
+    ///         (1..2, TokenKind::Major),
+    ///         (3..4, TokenKind::Minor),
+    ///     ],
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as [`parse`][VersionReq::parse].
+    #[allow(clippy::type_complexity)]
+    pub fn parse_verbose(text: &str) -> Result<(Self, Vec<(Range<usize>, TokenKind)>), Error> {
+        parse::version_req_verbose(text)
+    }
+
+    /// Whether this is the requirement with no ranges, [`VersionReq::STAR`],
+    /// which matches every release.
+    ///
+    /// The empty string, `*`, `x`, and `>=0` all parse to this requirement.
+    /// Other requirements that happen to match every release, such as `>=0 ||
+    /// ^1`, are not counted.
+    pub fn is_any(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Whether this requirement is written with wildcard syntax only, such as
+    /// `*`, `1.x`, or `1.* || 2.3.*`.
+    ///
+    /// Since `*` parses to the same requirement as the empty string and `>=0`,
+    /// those count as wildcards too, along with anything else for which
+    /// [`is_any`][VersionReq::is_any] is true. Hyphen ranges are not counted,
+    /// even between wildcards.
+    pub fn is_wildcard(&self) -> bool {
+        self.ranges.iter().all(|range| match range {
+            VersionRange::Hyphen(..) => false,
+            VersionRange::Simple(cmp) => cmp.op == Op::Wildcard,
+            VersionRange::Intersection(comparators) => {
+                comparators.iter().all(|cmp| cmp.op == Op::Wildcard)
+            }
+        })
+    }
+
+    /// A measure of the cost of evaluating this requirement, for rejecting
+    /// overly complex requirements from untrusted input before matching.
+    ///
+    /// This is the number of comparators that [`matches`][VersionReq::matches]
+    /// may check a version against, counting both endpoints of a hyphen range,
+    /// so that it grows with every `||` alternative and every comparator
+    /// within one. The requirement `*` scores 0, and `^1` scores 1.
+    ///
+    /// ```
+    /// use semver::VersionReq;
+    ///
+    /// let req = VersionReq::parse(">=1.2 <1.5 || 2.0.0 - 2.3 || ^3").unwrap();
+    /// assert_eq!(req.complexity(), 5);
+    /// ```
+    pub fn complexity(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|range| match range {
+                VersionRange::Hyphen(..) => 2,
+                VersionRange::Simple(_) => 1,
+                VersionRange::Intersection(comparators) => comparators.len(),
+            })
+            .sum()
+    }
+
     /// Evaluate whether the given `Version` satisfies the version requirement
     /// described by `self`.
     pub fn matches(&self, version: &Version) -> bool {
         eval::matches_req(self, version)
     }
+
+    /// Like [`matches`][VersionReq::matches], with a choice of whether a
+    /// version equal to the exclusive upper bound of a comparator matches.
+    ///
+    /// ```
+    /// use semver::{IncludeBoundsMode, Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse("~1.2.3").unwrap();
+    /// let version = Version::parse("1.3.0").unwrap();
+    /// assert!(!req.matches_with(&version, IncludeBoundsMode::Exclusive));
+    /// assert!(req.matches_with(&version, IncludeBoundsMode::Inclusive));
+    /// ```
+    pub fn matches_with(&self, version: &Version, mode: IncludeBoundsMode) -> bool {
+        eval::matches_req_with(self, version, mode)
+    }
+
+    /// The number of versions in `versions` that this requirement matches,
+    /// such as for showing how many published versions a requirement allows.
+    pub fn count_matching(&self, versions: &[Version]) -> usize {
+        versions
+            .iter()
+            .filter(|version| eval::matches_req(self, version))
+            .count()
+    }
+
+    /// A closure that owns this requirement and tells whether it matches a
+    /// version, for passing to `filter` or `retain` where the requirement
+    /// would otherwise need to outlive a local borrow.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    ///
+    /// let mut versions = vec![Version::new(1, 2, 0), Version::new(2, 0, 0)];
+    /// versions.retain(VersionReq::parse("^1").unwrap().into_predicate());
+    /// assert_eq!(versions, [Version::new(1, 2, 0)]);
+    /// ```
+    pub fn into_predicate(self) -> impl Fn(&Version) -> bool {
+        move |version| eval::matches_req(&self, version)
+    }
+
+    /// Why this requirement does not match `version`, as a message for the
+    /// end user, or `None` if it matches.
+    ///
+    /// A pre-release version that lies within a range of the requirement but
+    /// is excluded by the rule for pre-releases (see [`Prerelease`]) gets a
+    /// message saying so.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse(">=2.0.0").unwrap();
+    ///
+    /// let version = Version::parse("1.5.0").unwrap();
+    /// let message = req.explain_mismatch(&version).unwrap();
+    /// assert_eq!(message, "1.5.0 is not within >=2.0.0");
+    ///
+    /// let version = Version::parse("2.1.0-rc.1").unwrap();
+    /// let message = req.explain_mismatch(&version).unwrap();
+    /// assert_eq!(
+    ///     message,
+    ///     "2.1.0-rc.1 is a pre-release, and no comparator in >=2.0.0 allows pre-releases of 2.1.0",
+    /// );
+    ///
+    /// assert_eq!(req.explain_mismatch(&Version::new(2, 1, 0)), None);
+    /// ```
+    pub fn explain_mismatch(&self, version: &Version) -> Option<String> {
+        let message = match self.matches_with_reason(version) {
+            MatchReason::Matched => return None,
+            MatchReason::NoRangeMatched => format!("{} is not within {}", version, self),
+            MatchReason::PrereleaseRejected => format!(
+                "{} is a pre-release, and no comparator in {} allows pre-releases of {}.{}.{}",
+                version, self, version.major, version.minor, version.patch,
+            ),
+            #[cfg(no_non_exhaustive)]
+            MatchReason::__NonExhaustive => unreachable!(),
+        };
+        Some(message)
+    }
+
+    /// Like [`matches`][VersionReq::matches], but also letting through
+    /// pre-release versions on the given channels, even if no comparator
+    /// allows pre-releases of their major.minor.patch.
+    ///
+    /// The channel of a pre-release is its first dot-separated identifier,
+    /// such as `rc` for `1.2.3-rc.1`, and must equal one of `allowed_channels`
+    /// exactly. The version must still be within the requirement's ranges.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse("^1.2").unwrap();
+    /// let rc = Version::parse("1.5.0-rc.1").unwrap();
+    /// let alpha = Version::parse("1.5.0-alpha.1").unwrap();
+    ///
+    /// assert!(!req.matches(&rc));
+    /// assert!(req.matches_with_pre_allowlist(&rc, &["rc"]));
+    /// assert!(!req.matches_with_pre_allowlist(&alpha, &["rc"]));
+    /// ```
+    pub fn matches_with_pre_allowlist(&self, version: &Version, allowed_channels: &[&str]) -> bool {
+        if !eval::matches_ranges(self, version, IncludeBoundsMode::Exclusive) {
+            return false;
+        }
+        if version.pre.is_empty() || eval::pre_is_allowed(self, version) {
+            return true;
+        }
+        let channel = version.pre.as_str().split('.').next().unwrap();
+        allowed_channels.contains(&channel)
+    }
+
+    /// Like [`matches`][VersionReq::matches], but telling apart the two ways
+    /// that a version can fail to match.
+    ///
+    /// ```
+    /// use semver::{MatchReason, Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse(">=1.0.0").unwrap();
+    /// let reason = |text| req.matches_with_reason(&Version::parse(text).unwrap());
+    ///
+    /// assert_eq!(reason("1.5.0"), MatchReason::Matched);
+    /// assert_eq!(reason("0.9.0"), MatchReason::NoRangeMatched);
+    /// assert_eq!(reason("1.5.0-rc.1"), MatchReason::PrereleaseRejected);
+    /// ```
+    pub fn matches_with_reason(&self, version: &Version) -> MatchReason {
+        if !eval::matches_ranges(self, version, IncludeBoundsMode::Exclusive) {
+            MatchReason::NoRangeMatched
+        } else if !version.pre.is_empty() && !eval::pre_is_allowed(self, version) {
+            MatchReason::PrereleaseRejected
+        } else {
+            MatchReason::Matched
+        }
+    }
+
+    /// The smallest version above the range of versions matched by this
+    /// requirement, for example `2.0.0` for `^1.2.3` and `1.3.0` for `~1.2`.
+    ///
+    /// Returns `None` if the requirement is not bounded above (`>=1`, `*`) or
+    /// if it consists of more than one `||` alternative.
+    ///
+    /// For requirements whose upper end is inclusive, such as `<=1.2.3`, this
+    /// is the lowest version of greater precedence, namely `1.2.4-0`.
+    pub fn exclusive_upper_bound(&self) -> Option<Version> {
+        if self.ranges.len() != 1 {
+            return None;
+        }
+        match eval::range_bounds(&self.ranges[0]).1 {
+            Bound::Included(upper) => eval::successor(&upper),
+            Bound::Excluded(upper) => Some(upper),
+            Bound::Unbounded => None,
+        }
+    }
+
+    /// Pin this requirement to one version it matches, such as when writing a
+    /// lockfile: the requirement `=version`, or `None` if `version` does not
+    /// match `self`.
+    ///
+    /// Any pre-release of `version` is kept in the comparator. Build metadata
+    /// is left out, so the pin also matches other builds of the same version.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse("^1.2").unwrap();
+    /// let pin = req.pinned_to(&Version::new(1, 4, 0)).unwrap();
+    /// assert_eq!(pin.to_string(), "=1.4.0");
+    ///
+    /// assert_eq!(req.pinned_to(&Version::new(2, 0, 0)), None);
+    /// ```
+    pub fn pinned_to(&self, version: &Version) -> Option<VersionReq> {
+        if self.matches(version) {
+            Some(version.single_req(Op::Exact))
+        } else {
+            None
+        }
+    }
+
+    /// Combine requirements into one that is equivalent to joining them with
+    /// `||`.
+    ///
+    /// A requirement with no ranges, such as `*`, becomes a `>=0.0.0`
+    /// alternative, so that the pre-release tags of the other requirements
+    /// still let their pre-releases match: the union of `>=1.2.3-rc.1` and `*`
+    /// matches `1.2.3-rc.1`. The union of no requirements at all is `<0.0.0`,
+    /// which matches nothing, rather than [`VersionReq::STAR`].
+    pub fn union_all<I: IntoIterator<Item = VersionReq>>(reqs: I) -> VersionReq {
+        let mut ranges = Vec::new();
+        let mut any = false;
+        for req in reqs {
+            if req.ranges.is_empty() {
+                let zero = Version::new(0, 0, 0).comparator(Op::GreaterEq);
+                ranges.push(VersionRange::Simple(zero));
+            } else {
+                ranges.extend(req.ranges);
+            }
+            any = true;
+        }
+        if any {
+            parse::version_req_from_parsed(ranges)
+        } else {
+            interval::to_req(Vec::new())
+        }
+    }
+
+    /// Remove `||` alternatives that are structurally equal to an earlier
+    /// one, keeping the first of each in its original position.
+    ///
+    /// Alternatives that are written differently but match the same versions,
+    /// such as `^1` and `>=1.0.0 <2.0.0`, are both kept.
+    ///
+    /// ```
+    /// use semver::VersionReq;
+    ///
+    /// let req = VersionReq::parse("^1 || ^2").unwrap();
+    /// let mut union = VersionReq::union_all(vec![req.clone(), req.clone()]);
+    /// assert_eq!(union.to_string(), "^1 || ^2 || ^1 || ^2");
+    ///
+    /// union.dedup();
+    /// assert_eq!(union, req);
+    /// ```
+    pub fn dedup(&mut self) {
+        let ranges = mem::replace(&mut self.ranges, Vec::new());
+        for range in ranges {
+            if !self.ranges.contains(&range) {
+                self.ranges.push(range);
+            }
+        }
+    }
+
+    /// Take apart the requirement into the comparators of all of its `||`
+    /// alternatives, in order, without cloning them.
+    ///
+    /// A hyphen range becomes two comparators: its left endpoint with
+    /// [`Op::GreaterEq`] and its right endpoint with [`Op::LessEq`], which
+    /// together match the same versions as the range. Which alternative each
+    /// comparator came from is not kept, so for a requirement with several
+    /// alternatives the comparators are no longer equivalent to it when
+    /// joined with spaces. The requirement `*` has no comparators.
+    ///
+    /// ```
+    /// use semver::{Op, VersionReq};
+    ///
+    /// let req = VersionReq::parse("1.2.3 - 2.0.0 || ^3").unwrap();
+    /// let comparators = req.into_comparators();
+    /// assert_eq!(comparators.len(), 3);
+    /// assert_eq!(comparators[0].to_string(), ">=1.2.3");
+    /// assert_eq!(comparators[1].to_string(), "<=2.0.0");
+    /// assert_eq!(comparators[2].op, Op::Caret);
+    /// ```
+    pub fn into_comparators(self) -> Vec<Comparator> {
+        let mut out = Vec::with_capacity(self.complexity());
+        for range in self.ranges {
+            VersionReq::push_comparators(range, &mut out);
+        }
+        out
+    }
+
+    /// Combine requirements into one that matches the versions matched by all
+    /// of them, like joining their comparators with spaces.
+    ///
+    /// Each `||` alternative of the result joins one alternative from each
+    /// requirement, so the number of alternatives is the product of their
+    /// numbers. Within an alternative, a pre-release version is allowed by a
+    /// pre-release tag on any one comparator, as usual, so `>=1.0.0-rc.1`
+    /// combined with `<2` matches `1.0.0-rc.2`, even though `<2` on its own
+    /// does not. Requirements with no ranges, such as `*`, are skipped, and
+    /// the intersection of no requirements is [`VersionReq::STAR`].
+    ///
+    /// ```
+    /// use semver::VersionReq;
+    ///
+    /// let a = VersionReq::parse("^1.2 || ^2").unwrap();
+    /// let b = VersionReq::parse("<2.5").unwrap();
+    /// let req = VersionReq::intersect_all(vec![a, b]);
+    /// assert_eq!(req.to_string(), "^1.2 <2.5 || ^2 <2.5");
+    /// ```
+    pub fn intersect_all<I: IntoIterator<Item = VersionReq>>(reqs: I) -> VersionReq {
+        let mut product = vec![Vec::new()];
+        for req in reqs {
+            if req.ranges.is_empty() {
+                continue;
+            }
+            let mut next = Vec::new();
+            for comparators in &product {
+                for range in &req.ranges {
+                    let mut comparators = comparators.clone();
+                    VersionReq::push_comparators(range.clone(), &mut comparators);
+                    next.push(comparators);
+                }
+            }
+            product = next;
+        }
+        let ranges = product
+            .into_iter()
+            .filter(|comparators| !comparators.is_empty())
+            .map(|mut comparators| {
+                if comparators.len() == 1 {
+                    VersionRange::Simple(comparators.remove(0))
+                } else {
+                    VersionRange::Intersection(comparators)
+                }
+            })
+            .collect();
+        VersionReq { ranges }
+    }
+
+    // The comparators that together match the same as `range`. The endpoints
+    // of a hyphen range are matched as `>=` and `<=` respectively.
+    fn push_comparators(range: VersionRange, out: &mut Vec<Comparator>) {
+        match range {
+            VersionRange::Simple(cmp) => out.push(cmp),
+            VersionRange::Hyphen(left, right) => {
+                out.push(Comparator {
+                    op: Op::GreaterEq,
+                    ..left
+                });
+                out.push(Comparator {
+                    op: Op::LessEq,
+                    ..right
+                });
+            }
+            VersionRange::Intersection(comparators) => out.extend(comparators),
+        }
+    }
+
+    /// Rewrite comparators that pin a range to a single version into an exact
+    /// comparator, such as `>=1.2.3 <=1.2.3` or `1.2.3 - 1.2.3` into
+    /// `=1.2.3`. The result matches the same versions as `self`.
+    ///
+    /// Within a range with other comparators, the pair is replaced by one
+    /// exact comparator in place of the first of them, and the others are
+    /// kept: `>=1 <=1.2.3 >=1.2.3` becomes `>=1 =1.2.3`. Partial versions are
+    /// paired the same way, so `>=1.2 <=1.2` becomes `=1.2`.
+    ///
+    /// This is useful after [`intersect_all`][VersionReq::intersect_all],
+    /// which joins comparators without simplifying them. The results of
+    /// [`complement`][VersionReq::complement] and
+    /// [`difference`][VersionReq::difference] already use exact comparators
+    /// for single versions.
+    ///
+    /// ```
+    /// use semver::VersionReq;
+    ///
+    /// let req = VersionReq::parse(">=1.2.3 <=1.2.3 || ^2").unwrap();
+    /// assert_eq!(req.simplify().to_string(), "=1.2.3 || ^2");
+    /// ```
+    pub fn simplify(&self) -> VersionReq {
+        let same_version = |a: &Comparator, b: &Comparator| {
+            a.major == b.major && a.minor == b.minor && a.patch == b.patch && a.pre == b.pre
+        };
+        // Whether `a` and `b` are `>=` and `<=` of the same version, in either
+        // order.
+        let pins = |a: &Comparator, b: &Comparator| {
+            let ops = (a.op == Op::GreaterEq && b.op == Op::LessEq)
+                || (a.op == Op::LessEq && b.op == Op::GreaterEq);
+            ops && same_version(a, b)
+        };
+
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|range| match range {
+                VersionRange::Hyphen(left, right) if same_version(left, right) => {
+                    VersionRange::Simple(Comparator {
+                        op: Op::Exact,
+                        ..left.clone()
+                    })
+                }
+                VersionRange::Intersection(comparators) => {
+                    let mut out: Vec<Comparator> = Vec::with_capacity(comparators.len());
+                    for cmp in comparators {
+                        match out.iter_mut().find(|prev| pins(prev, cmp)) {
+                            Some(prev) => prev.op = Op::Exact,
+                            None => out.push(cmp.clone()),
+                        }
+                    }
+                    if out.len() == 1 {
+                        VersionRange::Simple(out.remove(0))
+                    } else {
+                        VersionRange::Intersection(out)
+                    }
+                }
+                _ => range.clone(),
+            })
+            .collect();
+        VersionReq { ranges }
+    }
+
+    /// A requirement matching the versions that `self` does not match. For
+    /// example the complement of `^1.2 || ^3` is `<1.2.0 || >=2.0.0 <3.0.0 ||
+    /// >=4.0.0`. The complement of `*` is `<0.0.0`, which matches nothing.
+    ///
+    /// Pre-release versions are subject to the usual rule on both sides: a
+    /// pre-release version matches only if some comparator with the same
+    /// major.minor.patch has a pre-release tag, so a pre-release version may
+    /// be matched by neither `self` nor its complement. Release versions are
+    /// matched by exactly one of them.
+    pub fn complement(&self) -> VersionReq {
+        interval::to_req(interval::complement(&interval::from_req(self)))
+    }
+
+    /// A requirement matching the versions matched by `self` but not by
+    /// `other`.
+    ///
+    /// The two requirements are treated as sets of intervals of versions, and
+    /// the result may consist of several `||` alternatives. For example `>=1
+    /// <4` minus `^2` is `>=1.0.0 <2.0.0 || >=3.0.0 <4.0.0`. If no version
+    /// remains, the result is `<0.0.0`, which matches nothing.
+    ///
+    /// Pre-release versions are accounted for only at interval endpoints. The
+    /// result is subject to the usual rule that a pre-release version matches
+    /// only if some comparator with the same major.minor.patch has a
+    /// pre-release tag, so pre-release versions near the edges of the result
+    /// may be matched differently than by `self` and `other`.
+    pub fn difference(&self, other: &VersionReq) -> VersionReq {
+        let this = interval::from_req(self);
+        let other = interval::complement(&interval::from_req(other));
+        interval::to_req(interval::intersect(&this, &other))
+    }
+
+    /// How permissive `self` is compared to `other`: `Less` if it matches a
+    /// strict subset of the versions that `other` matches, `Greater` if a
+    /// strict superset, `Equal` if the same versions, and `None` if each
+    /// matches some version that the other does not.
+    ///
+    /// This lets tooling tell that a new requirement is more restrictive than
+    /// the one it replaces. The requirements are compared as sets of intervals
+    /// of versions, so `^1.2` and `>=1.2.0 <2.0.0` are equally permissive.
+    ///
+    /// Pre-release versions count only where the usual rule lets them match:
+    /// `>=1.0.0-0 <2.0.0-0` is more permissive than `^1`, as it also matches
+    /// the pre-releases of 1.0.0, while `<0.0.1` and `<0.0.1-0` are equally
+    /// permissive, since neither matches a pre-release of 0.0.1. Comparators
+    /// on partial versions, such as `>1`, match a pre-release by its
+    /// major.minor.patch, and for those the pre-releases are compared only by
+    /// precedence.
+    ///
+    /// ```
+    /// use semver::VersionReq;
+    /// use std::cmp::Ordering;
+    ///
+    /// let old = VersionReq::parse("^1.2").unwrap();
+    /// let new = VersionReq::parse("^1.4").unwrap();
+    /// assert_eq!(new.permissiveness(&old), Some(Ordering::Less));
+    /// ```
+    pub fn permissiveness(&self, other: &VersionReq) -> Option<Ordering> {
+        match (
+            interval::is_subset(self, other),
+            interval::is_subset(other, self),
+        ) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+
+    /// Whether any version between `lower` and `upper` matches this
+    /// requirement, going by the range of versions it covers.
+    ///
+    /// As with [`difference`][VersionReq::difference], pre-release versions
+    /// are considered only at the endpoints of the requirement's ranges.
+    pub fn intersects_interval(&self, lower: Bound<&Version>, upper: Bound<&Version>) -> bool {
+        let cloned = |bound: Bound<&Version>| match bound {
+            Bound::Included(ver) => Bound::Included(ver.clone()),
+            Bound::Excluded(ver) => Bound::Excluded(ver.clone()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let interval = [(cloned(lower), cloned(upper))];
+        !interval::intersect(&interval::from_req(self), &interval).is_empty()
+    }
+
+    /// The smallest version matched by this requirement, for generating
+    /// boundary test cases.
+    ///
+    /// - An inclusive lower bound is itself the result: `1.2.3` for `^1.2.3`.
+    /// - Above an exclusive lower bound that is a release, the result is the
+    ///   next patch release: `1.2.4` for `>1.2.3`. The pre-releases of 1.2.4
+    ///   in between are not matched, as no comparator mentions a pre-release
+    ///   of 1.2.4.
+    /// - Above an exclusive lower bound that is a pre-release, the result is
+    ///   the smallest pre-release after it: `1.2.3-rc.1.0` for `>1.2.3-rc.1`.
+    /// - With no lower bound the result is `0.0.0`, or `0.0.0-0` where that
+    ///   matches.
+    /// - Where the bound is a pre-release that the requirement does not match,
+    ///   the result is the least version above it that does: `1.1.1` for `1
+    ///   >=1.1.1-alpha`, and `1.2.3-0` for `>1.2.2 <1.2.3-rc.1`.
+    /// - A pre-release that a partial comparator matches by its
+    ///   major.minor.patch counts too: `0.0.0-rc.1` for `=0.0.0-rc.1 ^0.0`.
+    ///
+    /// Returns `None` if the requirement matches nothing, such as `<0.0.0`.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse(">1.2.3 <2").unwrap();
+    /// assert_eq!(req.sample_min(), Some(Version::new(1, 2, 4)));
+    /// ```
+    pub fn sample_min(&self) -> Option<Version> {
+        interval::sample_min(self)
+    }
+
+    /// A large version matched by this requirement, for generating boundary
+    /// test cases.
+    ///
+    /// - An inclusive upper bound is itself the result: `1.4.0` for `<=1.4.0`.
+    /// - Below an exclusive upper bound, there is no greatest version, because
+    ///   the bound's pre-releases keep getting closer to it. The result is
+    ///   instead the greatest release below the bound, such as `1.2.2` for
+    ///   `<1.2.3` or `<1.2.3-rc.1`, and for `^1.2.3` it is
+    ///   <code>1.18446744073709551615.18446744073709551615</code>, with minor
+    ///   and patch at `u64::MAX`.
+    /// - With no upper bound the result is the version with every component at
+    ///   `u64::MAX`.
+    /// - A range that matches only pre-releases of one major.minor.patch below
+    ///   an exclusive bound, such as `>=1.2.3-alpha <1.2.3`, has no matching
+    ///   release below it, and the result is its smallest version.
+    ///
+    /// Returns `None` if the requirement matches nothing, such as `<0.0.0`.
+    pub fn sample_max(&self) -> Option<Version> {
+        interval::sample_max(self)
+    }
+
+    /// The versions on either side of every bound of every comparator in this
+    /// requirement, sorted by precedence, for generating test cases that hit
+    /// each edge of the matching logic.
+    ///
+    /// Partial versions are first rounded out to release boundaries, so the
+    /// bounds of `^1.2.3` are `1.2.3` and `2.0.0`, and those of `~1.2` are
+    /// `1.2.0` and `1.3.0`. For each bound version the result has:
+    ///
+    /// - the greatest release below it: `1.2.2` for `1.2.3`, and `1.2.2` for
+    ///   `1.2.3-rc.1` too;
+    /// - for a release, its `-0` pre-release, which is the lowest version with
+    ///   the same major.minor.patch: `1.2.3-0` for `1.2.3`;
+    /// - the bound version itself;
+    /// - for a pre-release, the lowest version above it: `1.2.3-rc.1.0` for
+    ///   `1.2.3-rc.1`;
+    /// - the lowest release above it: `1.2.4` for `1.2.3`, and `1.2.3` for
+    ///   `1.2.3-rc.1`.
+    ///
+    /// Bounds are taken from each comparator on its own, not only from the
+    /// ranges they combine into, and a requirement with no comparators, such
+    /// as `*`, has no boundary versions.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse("^1.2.3").unwrap();
+    /// let boundaries = req.boundary_versions();
+    /// assert_eq!(boundaries[..4], [
+    ///     Version::parse("1.2.2").unwrap(),
+    ///     Version::parse("1.2.3-0").unwrap(),
+    ///     Version::parse("1.2.3").unwrap(),
+    ///     Version::parse("1.2.4").unwrap(),
+    /// ]);
+    /// ```
+    pub fn boundary_versions(&self) -> Vec<Version> {
+        interval::boundary_versions(self)
+    }
+}
+
+impl Default for IncludeBoundsMode {
+    fn default() -> Self {
+        IncludeBoundsMode::Exclusive
+    }
 }
 
 /// The default VersionReq is the same as [`VersionReq::STAR`].
@@ -500,6 +2148,60 @@ impl Comparator {
     pub fn matches(&self, version: &Version) -> bool {
         eval::matches_comparator(self, version)
     }
+
+    /// A requirement matching the versions that this comparator does not
+    /// match. For example the negation of `>=1.2.3` is `<1.2.3`, and that of
+    /// `^1.2.3` is `<1.2.3 || >=2.0.0`.
+    ///
+    /// The usual pre-release rule applies to both the comparator and its
+    /// negation, so a pre-release version may be matched by neither of them:
+    /// `1.2.3-rc.1` matches neither `>=1.2.3` nor `<1.2.3`. A comparator that
+    /// has a pre-release keeps it in its negation, so `>=1.2.3-rc.1` negates
+    /// to `<1.2.3-rc.1`, which matches `1.2.3-rc.0`.
+    pub fn negate(&self) -> VersionReq {
+        let set = interval::from_comparator(self);
+        interval::to_req(interval::complement(&set))
+    }
+
+    /// Whether some version matches both this comparator and `other`.
+    ///
+    /// Comparators whose ranges only touch do not intersect when either end
+    /// is exclusive: `<2.0.0` and `>=2.0.0` do not, while `<=2.0.0` and
+    /// `>=2.0.0` do, at `2.0.0`. Each comparator is held to the usual
+    /// pre-release rule on its own, so `>1.2.3-rc.1` and `<1.2.3` do not
+    /// intersect: the versions between them are pre-releases of 1.2.3, which
+    /// `<1.2.3` does not match.
+    pub fn intersects(&self, other: &Comparator) -> bool {
+        interval::comparators_intersect(self, other)
+    }
+}
+
+impl PaddedVersion {
+    /// The numeric version, for comparisons and matching.
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// The text that the version was parsed from.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_version(self) -> Version {
+        self.version
+    }
+}
+
+impl PartialVersion {
+    /// Create `PartialVersion` by parsing from string representation.
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`Version::parse`], except that the minor and patch
+    /// numbers may be missing.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        PartialVersion::from_str(text)
+    }
 }
 
 impl Prerelease {
@@ -537,3 +2239,33 @@ impl BuildMetadata {
         self.identifier.is_empty()
     }
 }
+
+/// Bucket versions by their major and minor numbers, such as for the sections
+/// of a changelog. The versions in each bucket are sorted by [`Ord`], from
+/// lowest to highest.
+///
+/// ```
+/// use semver::Version;
+///
+/// let versions = ["1.1.0", "1.0.1", "1.1.0-rc.1", "1.0.0"]
+///     .iter()
+///     .map(|v| Version::parse(v).unwrap())
+///     .collect::<Vec<_>>();
+/// let groups = semver::group_by_minor(&versions);
+///
+/// assert_eq!(groups.keys().collect::<Vec<_>>(), [&(1, 0), &(1, 1)]);
+/// assert_eq!(groups[&(1, 1)][0].to_string(), "1.1.0-rc.1");
+/// ```
+pub fn group_by_minor(versions: &[Version]) -> BTreeMap<(u64, u64), Vec<&Version>> {
+    let mut groups = BTreeMap::new();
+    for version in versions {
+        groups
+            .entry((version.major, version.minor))
+            .or_insert_with(Vec::new)
+            .push(version);
+    }
+    for group in groups.values_mut() {
+        group.sort();
+    }
+    groups
+}
@@ -1,8 +1,21 @@
+use crate::alloc::string::String;
 use crate::{backport::*, VersionRange};
 use crate::error::{ErrorKind, Position};
 use crate::identifier::Identifier;
-use crate::{BuildMetadata, Comparator, Op, Prerelease, Version, VersionReq};
-use core::str::FromStr;
+use crate::{eval, interval};
+use crate::{
+    BuildMetadata, Comparator, GitDescribe, Op, PaddedVersion, PartialVersion, Prerelease,
+    TokenKind, Version, VersionReq,
+};
+use core::cmp;
+use core::ops::Range;
+use core::str::{self, FromStr};
+use core::u32;
+
+#[cfg(feature = "pep440")]
+use crate::PepVersion;
+#[cfg(feature = "four_component")]
+use crate::QuadVersion;
 
 /// Error parsing a SemVer version or version requirement.
 ///
@@ -25,6 +38,21 @@ pub struct Error {
 impl FromStr for Version {
     type Err = Error;
 
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (version, pos, text) = version_prefix(text)?;
+
+        if let Some(unexpected) = text.chars().next() {
+            return Err(Error::new(ErrorKind::UnexpectedCharAfter(pos, unexpected)));
+        }
+
+        Ok(version)
+    }
+}
+
+#[cfg(feature = "four_component")]
+impl FromStr for QuadVersion {
+    type Err = Error;
+
     fn from_str(text: &str) -> Result<Self, Self::Err> {
         if text.is_empty() {
             return Err(Error::new(ErrorKind::Empty));
@@ -40,38 +68,97 @@ impl FromStr for Version {
 
         pos = Position::Patch;
         let (patch, text) = numeric_identifier(text, pos)?;
+        let text = dot(text, pos)?;
 
-        if text.is_empty() {
-            return Ok(Version::new(major, minor, patch));
+        pos = Position::Revision;
+        let (revision, text) = numeric_identifier(text, pos)?;
+
+        if let Some(unexpected) = text.chars().next() {
+            return Err(Error::new(ErrorKind::UnexpectedCharAfter(pos, unexpected)));
         }
 
-        let (pre, text) = if let Some(text) = text.strip_prefix('-') {
-            pos = Position::Pre;
-            let (pre, text) = prerelease_identifier(text)?;
-            if pre.is_empty() {
-                return Err(Error::new(ErrorKind::EmptySegment(pos)));
+        Ok(QuadVersion::new(major, minor, patch, revision))
+    }
+}
+
+#[cfg(feature = "pep440")]
+impl FromStr for PepVersion {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (epoch, text) = match text.find('!') {
+            Some(i) => {
+                let pos = Position::Epoch;
+                let (epoch, rest) = numeric_identifier(&text[..i], pos)?;
+                if let Some(unexpected) = rest.chars().next() {
+                    return Err(Error::new(ErrorKind::UnexpectedCharAfter(pos, unexpected)));
+                }
+                (epoch, &text[i + 1..])
             }
-            (pre, text)
+            None => (0, text),
+        };
+        let version = Version::from_str(text)?;
+        Ok(PepVersion::new(epoch, version))
+    }
+}
+
+impl FromStr for PartialVersion {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text.is_empty() {
+            return Err(Error::new(ErrorKind::Empty));
+        }
+
+        let mut pos = Position::Major;
+        let (major, text) = numeric_identifier(text, pos)?;
+
+        let (minor, text) = if let Some(text) = text.strip_prefix('.') {
+            pos = Position::Minor;
+            let (minor, text) = numeric_identifier(text, pos)?;
+            (Some(minor), text)
         } else {
-            (Prerelease::EMPTY, text)
+            (None, text)
         };
 
-        let (build, text) = if let Some(text) = text.strip_prefix('+') {
-            pos = Position::Build;
-            let (build, text) = build_identifier(text)?;
-            if build.is_empty() {
-                return Err(Error::new(ErrorKind::EmptySegment(pos)));
+        let (patch, text) = match text.strip_prefix('.') {
+            Some(text) if minor.is_some() => {
+                pos = Position::Patch;
+                let (patch, text) = numeric_identifier(text, pos)?;
+                (Some(patch), text)
             }
-            (build, text)
-        } else {
-            (BuildMetadata::EMPTY, text)
+            _ => (None, text),
+        };
+
+        let (pre, text) = match text.strip_prefix('-') {
+            Some(text) if patch.is_some() => {
+                pos = Position::Pre;
+                let (pre, text) = prerelease_identifier(text)?;
+                if pre.is_empty() {
+                    return Err(Error::new(ErrorKind::EmptySegment(pos)));
+                }
+                (pre, text)
+            }
+            _ => (Prerelease::EMPTY, text),
+        };
+
+        let (build, text) = match text.strip_prefix('+') {
+            Some(text) if patch.is_some() => {
+                pos = Position::Build;
+                let (build, text) = build_identifier(text)?;
+                if build.is_empty() {
+                    return Err(Error::new(ErrorKind::EmptySegment(pos)));
+                }
+                (build, text)
+            }
+            _ => (BuildMetadata::EMPTY, text),
         };
 
         if let Some(unexpected) = text.chars().next() {
             return Err(Error::new(ErrorKind::UnexpectedCharAfter(pos, unexpected)));
         }
 
-        Ok(Version {
+        Ok(PartialVersion {
             major,
             minor,
             patch,
@@ -86,23 +173,12 @@ impl FromStr for VersionReq {
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
         let text = text.trim_start_matches(' ');
-        if text.is_empty() {
+        if text.is_empty() || text.trim_end_matches(' ').eq_ignore_ascii_case("latest") {
             return Ok(VersionReq::STAR);
         }
-        if let Some((ch, text)) = wildcard(text) {
-            let rest = text.trim_start_matches(' ');
-            if rest.is_empty() {
-                return Ok(VersionReq::STAR);
-            } else if rest.starts_with(',') {
-                return Err(Error::new(ErrorKind::WildcardNotTheOnlyComparator(ch)));
-            } else {
-                return Err(Error::new(ErrorKind::UnexpectedAfterWildcard));
-            }
-        }
-
         let mut ranges = Vec::new();
-        parser_version_req(text, &mut ranges)?;
-        Ok(VersionReq { ranges })
+        parser_first_alternative(text, &mut ranges)?;
+        Ok(version_req_from_parsed(ranges))
     }
 }
 
@@ -120,6 +196,25 @@ impl FromStr for Comparator {
     }
 }
 
+impl FromStr for Op {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "=" => Ok(Op::Exact),
+            ">" => Ok(Op::Greater),
+            ">=" => Ok(Op::GreaterEq),
+            "<" => Ok(Op::Less),
+            "<=" => Ok(Op::LessEq),
+            "~" => Ok(Op::Tilde),
+            "~>" => Ok(Op::Pessimistic),
+            "^" => Ok(Op::Caret),
+            "*" => Ok(Op::Wildcard),
+            _ => Err(Error::new(ErrorKind::UnrecognizedOp)),
+        }
+    }
+}
+
 impl FromStr for Prerelease {
     type Err = Error;
 
@@ -148,12 +243,548 @@ impl Error {
     fn new(kind: ErrorKind) -> Self {
         Error { kind }
     }
+
+    /// Whether a more lenient parser might accept the input, so that it is
+    /// worth retrying with one rather than giving up:
+    ///
+    /// - a leading zero in the major, minor, or patch number, as in `01.2.3`,
+    ///   which [`Version::parse_preserve_zeros`] accepts;
+    /// - a missing minor or patch number, as in `1` or `1.2`, which could be
+    ///   filled in with zero;
+    /// - whitespace before or after the version, as in `1.2.3\n`, which
+    ///   [`Version::parse_trimmed`] accepts.
+    ///
+    /// Other errors, such as an empty string, an illegal character, or a
+    /// numeric overflow, are not recoverable.
+    ///
+    /// The input ending right after the major number, as in `1`, is reported
+    /// the same way as it ending where the major number should be, as in `^`,
+    /// `>=`, or `1.2.3 - `. Both are classed recoverable, even though there is
+    /// nothing to fill in for the second. Whether an error is recoverable
+    /// says nothing of whether any one lenient parser succeeds on the input,
+    /// since the input may have further errors after the first one.
+    pub fn is_recoverable(&self) -> bool {
+        match self.kind {
+            ErrorKind::LeadingZero(pos) => pos != Position::Pre,
+            ErrorKind::UnexpectedEnd(pos) => pos != Position::Pre && pos != Position::Build,
+            ErrorKind::UnexpectedChar(pos, ch) => {
+                pos == Position::Major && ch.is_ascii_whitespace()
+            }
+            ErrorKind::UnexpectedCharAfter(pos, ch) => {
+                pos != Position::Major && pos != Position::Minor && ch.is_ascii_whitespace()
+            }
+            ErrorKind::Empty
+            | ErrorKind::ExpectedCommaFound(..)
+            | ErrorKind::Overflow(_)
+            | ErrorKind::EmptySegment(_)
+            | ErrorKind::IllegalCharacter(_)
+            | ErrorKind::WildcardNotTheOnlyComparator(_)
+            | ErrorKind::UnexpectedAfterWildcard
+            | ErrorKind::ExcessiveComparators
+            | ErrorKind::ExpectedComparator(_)
+            | ErrorKind::UnrecognizedOp
+            | ErrorKind::PrereleaseNotAllowed(_)
+            | ErrorKind::InvertedHyphenRange
+            | ErrorKind::OperatorInHyphenRange
+            | ErrorKind::PrereleaseOnPartial(_) => false,
+        }
+    }
+}
+
+/// Parser for a [`VersionReq`] that arrives in pieces, such as from a socket.
+///
+/// ```
+/// use semver::{VersionReq, VersionReqParser};
+///
+/// let mut parser = VersionReqParser::new();
+/// for chunk in &["^1.2", ".3 |", "| >=2", ".1"] {
+///     parser.feed(chunk).unwrap();
+/// }
+/// let req = parser.finish().unwrap();
+/// assert_eq!(req, VersionReq::parse("^1.2.3 || >=2.1").unwrap());
+/// ```
+///
+/// Each `||` alternative is parsed as soon as the `||` after it has been fed,
+/// so only the text of the alternative in progress is buffered, and an error
+/// in a complete alternative is returned by `feed` right away. The result of
+/// `finish` is the same as parsing all of the text at once with
+/// [`VersionReq::parse`], however it was split into chunks.
+#[derive(Default, Debug)]
+pub struct VersionReqParser {
+    ranges: Vec<VersionRange>,
+    pending: String,
+    started: bool,
+    failed: Option<Error>,
+}
+
+impl VersionReqParser {
+    pub fn new() -> Self {
+        VersionReqParser::default()
+    }
+
+    /// Append the next piece of the requirement's text.
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`VersionReq::parse`], for an alternative that is known
+    /// to be complete. After an error, every further call to `feed` or
+    /// `finish` returns the same error.
+    pub fn feed(&mut self, chunk: &str) -> Result<(), Error> {
+        if let Some(err) = &self.failed {
+            return Err(Error::new(err.kind));
+        }
+        // A `||` may be split between the end of the previous chunk and the
+        // start of this one.
+        let mut start = self.pending.len();
+        if self.pending.ends_with('|') {
+            start -= 1;
+        }
+        self.pending.push_str(chunk);
+        while let Some(i) = self.pending[start..].find("||") {
+            let end = start + i + 2;
+            let alternative = &self.pending[..end];
+            let result = if self.started {
+                parser_version_req(alternative, &mut self.ranges)
+            } else {
+                parser_first_alternative(alternative.trim_start_matches(' '), &mut self.ranges)
+            };
+            if let Err(err) = result {
+                self.failed = Some(Error::new(err.kind));
+                return Err(err);
+            }
+            self.started = true;
+            self.pending.drain(..end);
+            start = 0;
+        }
+        Ok(())
+    }
+
+    /// Parse the rest of the text and return the requirement.
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`VersionReq::parse`].
+    pub fn finish(mut self) -> Result<VersionReq, Error> {
+        if let Some(err) = &self.failed {
+            return Err(Error::new(err.kind));
+        }
+        if !self.started {
+            return VersionReq::from_str(&self.pending);
+        }
+        parser_version_req(&self.pending, &mut self.ranges)?;
+        Ok(version_req_from_parsed(self.ranges))
+    }
+}
+
+// Parse a version from the start of `text`, stopping at the first character
+// that cannot continue it. Also returns the position of the last part parsed,
+// for reporting what the leftover text comes after.
+pub(crate) fn version_prefix(text: &str) -> Result<(Version, Position, &str), Error> {
+    if text.is_empty() {
+        return Err(Error::new(ErrorKind::Empty));
+    }
+
+    let mut pos = Position::Major;
+    let (major, text) = numeric_identifier(text, pos)?;
+    let text = dot(text, pos)?;
+
+    pos = Position::Minor;
+    let (minor, text) = numeric_identifier(text, pos)?;
+    let text = dot(text, pos)?;
+
+    pos = Position::Patch;
+    let (patch, text) = numeric_identifier(text, pos)?;
+
+    let (pre, text) = if let Some(text) = text.strip_prefix('-') {
+        pos = Position::Pre;
+        let (pre, text) = prerelease_identifier(text)?;
+        if pre.is_empty() {
+            return Err(Error::new(ErrorKind::EmptySegment(pos)));
+        }
+        (pre, text)
+    } else {
+        (Prerelease::EMPTY, text)
+    };
+
+    let (build, text) = if let Some(text) = text.strip_prefix('+') {
+        pos = Position::Build;
+        let (build, text) = build_identifier(text)?;
+        if build.is_empty() {
+            return Err(Error::new(ErrorKind::EmptySegment(pos)));
+        }
+        (build, text)
+    } else {
+        (BuildMetadata::EMPTY, text)
+    };
+
+    let version = Version {
+        major,
+        minor,
+        patch,
+        pre,
+        build,
+    };
+    Ok((version, pos, text))
+}
+
+pub(crate) fn version_from_bytes(bytes: &[u8]) -> Result<Version, Error> {
+    let ascii_len = bytes
+        .iter()
+        .position(|b| !b.is_ascii())
+        .unwrap_or(bytes.len());
+    // SAFETY: ASCII bytes are always valid UTF-8.
+    let text = unsafe { str::from_utf8_unchecked(&bytes[..ascii_len]) };
+    if ascii_len == bytes.len() {
+        return Version::from_str(text);
+    }
+
+    // Report the non-ASCII byte unless the ASCII text before it is already
+    // invalid, in which case the error is the same as for a string.
+    if text.is_empty() {
+        return Err(Error::new(ErrorKind::IllegalCharacter(Position::Major)));
+    }
+    match version_prefix(text) {
+        Ok((_version, pos, "")) => Err(Error::new(ErrorKind::IllegalCharacter(pos))),
+        Ok((_version, pos, rest)) => {
+            let unexpected = rest.chars().next().unwrap();
+            Err(Error::new(ErrorKind::UnexpectedCharAfter(pos, unexpected)))
+        }
+        Err(err) => match err.kind {
+            ErrorKind::UnexpectedEnd(pos) => Err(Error::new(ErrorKind::IllegalCharacter(pos))),
+            // The identifier that is empty is the one cut off by the
+            // non-ASCII byte, as in `1.2.3-\xff`, if the text is valid up to
+            // the delimiter before it.
+            ErrorKind::EmptySegment(pos)
+                if text.ends_with(|ch| ch == '.' || ch == '-' || ch == '+')
+                    && is_complete_version(&text[..text.len() - 1]) =>
+            {
+                Err(Error::new(ErrorKind::IllegalCharacter(pos)))
+            }
+            _ => Err(err),
+        },
+    }
+}
+
+fn is_complete_version(text: &str) -> bool {
+    match version_prefix(text) {
+        Ok((_version, _pos, rest)) => rest.is_empty(),
+        Err(_) => false,
+    }
+}
+
+pub(crate) fn version_git_describe(text: &str) -> Result<(Version, Option<GitDescribe>), Error> {
+    let mut parts = text.rsplitn(3, '-');
+    let sha = parts.next().and_then(|part| part.strip_prefix('g'));
+    let commits_ahead = parts.next();
+    let tag = parts.next();
+
+    if let (Some(sha), Some(commits_ahead), Some(tag)) = (sha, commits_ahead, tag) {
+        let is_sha = !sha.is_empty() && sha.bytes().all(|b| b.is_ascii_hexdigit());
+        let is_count =
+            !commits_ahead.is_empty() && commits_ahead.bytes().all(|b| b.is_ascii_digit());
+        if is_sha && is_count {
+            if let Ok(commits_ahead) = commits_ahead.parse() {
+                let describe = GitDescribe {
+                    commits_ahead,
+                    sha: String::from(sha),
+                };
+                return Ok((Version::from_str(tag)?, Some(describe)));
+            }
+        }
+    }
+
+    Ok((Version::from_str(text)?, None))
+}
+
+pub(crate) fn version_preserve_zeros(text: &str) -> Result<PaddedVersion, Error> {
+    // Strip the leading zeros from the major, minor, and patch numbers, then
+    // parse as usual.
+    let end = text.find(|ch| ch == '-' || ch == '+').unwrap_or(text.len());
+    let mut stripped = String::with_capacity(text.len());
+    for (i, number) in text[..end].split('.').enumerate() {
+        if i > 0 {
+            stripped.push('.');
+        }
+        if number.len() > 1 && number.bytes().all(|b| b.is_ascii_digit()) {
+            match number.trim_start_matches('0') {
+                "" => stripped.push('0'),
+                trimmed => stripped.push_str(trimmed),
+            }
+        } else {
+            stripped.push_str(number);
+        }
+    }
+    stripped.push_str(&text[end..]);
+
+    let version = Version::from_str(&stripped)?;
+    Ok(PaddedVersion {
+        version,
+        text: String::from(text),
+    })
+}
+
+pub(crate) fn version_collect_errors(text: &str) -> Result<Version, Vec<(usize, Error)>> {
+    let err = match Version::from_str(text) {
+        Ok(version) => return Ok(version),
+        Err(err) => err,
+    };
+
+    // Parse the major.minor.patch, pre-release, and build metadata each on
+    // their own to find every one that is malformed. Neither of the first two
+    // can contain a `+`, and the first can't contain a `-`.
+    let (rest, build) = match text.find('+') {
+        Some(i) => (&text[..i], Some(i + 1)),
+        None => (text, None),
+    };
+    let (core, pre) = match rest.find('-') {
+        Some(i) => (&rest[..i], Some(i + 1)),
+        None => (rest, None),
+    };
+
+    let mut errors = Vec::new();
+    if let Err(err) = Version::from_str(core) {
+        errors.push((0, err));
+    }
+    if let Some(start) = pre {
+        if let Err(err) = identifier_part(&rest[start..], Position::Pre) {
+            errors.push((start, err));
+        }
+    }
+    if let Some(start) = build {
+        if let Err(err) = identifier_part(&text[start..], Position::Build) {
+            errors.push((start, err));
+        }
+    }
+
+    if errors.is_empty() {
+        errors.push((0, err));
+    }
+    Err(errors)
+}
+
+// The whole of a pre-release or build metadata that follows its `-` or `+`,
+// which unlike `Prerelease::new` and `BuildMetadata::new` must not be empty.
+fn identifier_part(text: &str, pos: Position) -> Result<(), Error> {
+    let (string, rest) = identifier(text, pos)?;
+    if string.is_empty() {
+        return Err(Error::new(ErrorKind::EmptySegment(pos)));
+    }
+    if !rest.is_empty() {
+        return Err(Error::new(ErrorKind::IllegalCharacter(pos)));
+    }
+    Ok(())
+}
+
+pub(crate) fn version_req_collect_errors(text: &str) -> Result<VersionReq, Vec<(usize, Error)>> {
+    let err = match VersionReq::from_str(text) {
+        Ok(req) => return Ok(req),
+        Err(err) => err,
+    };
+
+    // Parse each alternative on its own to find every one that is malformed.
+    // Only the first may be `*`, `latest`, or empty, as in a whole req; the
+    // others are parsed the way the whole parser does after a `||`.
+    let mut errors = Vec::new();
+    let mut offset = 0;
+    for (i, alternative) in text.split("||").enumerate() {
+        let trimmed = alternative.trim_start_matches(' ');
+        let result = if i == 0 {
+            VersionReq::from_str(trimmed).map(drop)
+        } else {
+            parser_version_req(alternative, &mut Vec::new())
+        };
+        if let Err(err) = result {
+            errors.push((offset + alternative.len() - trimmed.len(), err));
+        }
+        offset += alternative.len() + 2;
+    }
+
+    // Such as `* || 1`, where each alternative is fine on its own.
+    if errors.is_empty() {
+        errors.push((0, err));
+    }
+    Err(errors)
+}
+
+pub(crate) fn version_req_no_prerelease(text: &str) -> Result<VersionReq, Error> {
+    let (req, tokens) = version_req_verbose(text)?;
+    for (span, kind) in tokens {
+        if kind == TokenKind::Pre {
+            // The span starts after the `-` that introduces the pre-release.
+            let offset = cmp::min(span.start - 1, u32::MAX as usize) as u32;
+            return Err(Error::new(ErrorKind::PrereleaseNotAllowed(offset)));
+        }
+    }
+    Ok(req)
+}
+
+pub(crate) fn version_req_from_ranges(ranges: Vec<VersionRange>) -> Result<VersionReq, Error> {
+    for range in &ranges {
+        if let VersionRange::Hyphen(left, right) = range {
+            if !is_hyphen_endpoint(left) || !is_hyphen_endpoint(right) {
+                return Err(Error::new(ErrorKind::OperatorInHyphenRange));
+            }
+            let (lower, upper) = eval::range_bounds(range);
+            if interval::is_empty(&lower, &upper) {
+                return Err(Error::new(ErrorKind::InvertedHyphenRange));
+            }
+        }
+    }
+    Ok(VersionReq { ranges })
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn version_req_verbose(
+    text: &str,
+) -> Result<(VersionReq, Vec<(Range<usize>, TokenKind)>), Error> {
+    let req = VersionReq::from_str(text)?;
+
+    // The text is known to be a valid req, so from here on only the token
+    // boundaries need to be found.
+    let mut tokens = Vec::new();
+    let trimmed = text.trim_start_matches(' ');
+    if trimmed.trim_end_matches(' ').eq_ignore_ascii_case("latest") {
+        // An alias of `*`, which is reported as the major version.
+        let start = text.len() - trimmed.len();
+        tokens.push((start..start + "latest".len(), TokenKind::Major));
+        return Ok((req, tokens));
+    }
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start_matches(' ');
+        if rest.is_empty() {
+            break;
+        } else if rest.starts_with("||") {
+            let after = &rest[2..];
+            push_token(&mut tokens, text, rest, after, TokenKind::Or);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('-') {
+            push_token(&mut tokens, text, rest, after, TokenKind::Hyphen);
+            rest = after;
+        } else {
+            let after = comparator_tokens(text, rest, &mut tokens);
+            if after.len() == rest.len() {
+                // Not a token of any kind, which FromStr should have rejected.
+                let unexpected = rest.chars().next().unwrap();
+                return Err(Error::new(ErrorKind::UnexpectedChar(
+                    Position::Major,
+                    unexpected,
+                )));
+            }
+            rest = after;
+        }
+    }
+
+    Ok((req, tokens))
+}
+
+fn comparator_tokens<'a>(
+    text: &str,
+    input: &'a str,
+    tokens: &mut Vec<(Range<usize>, TokenKind)>,
+) -> &'a str {
+    let (_op, after) = op(input);
+    if after.len() < input.len() {
+        push_token(tokens, text, input, after, TokenKind::Operator);
+    }
+    let rest = after.trim_start_matches(' ');
+
+    let mut after = partial_component(rest);
+    push_token(tokens, text, rest, after, TokenKind::Major);
+    let mut has_patch = false;
+    for &kind in &[TokenKind::Minor, TokenKind::Patch] {
+        let rest = match after.strip_prefix('.') {
+            Some(rest) => rest,
+            None => break,
+        };
+        after = partial_component(rest);
+        push_token(tokens, text, rest, after, kind);
+        has_patch = kind == TokenKind::Patch;
+    }
+
+    if has_patch {
+        if let Some(rest) = after.strip_prefix('-') {
+            after = rest.trim_start_matches(is_identifier_char);
+            push_token(tokens, text, rest, after, TokenKind::Pre);
+        }
+        if let Some(rest) = after.strip_prefix('+') {
+            after = rest.trim_start_matches(is_identifier_char);
+            push_token(tokens, text, rest, after, TokenKind::Build);
+        }
+    }
+
+    after
+}
+
+fn partial_component(input: &str) -> &str {
+    match wildcard(input) {
+        Some((_, rest)) => rest,
+        None => input.trim_start_matches(|ch: char| ch.is_ascii_digit()),
+    }
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '-' || ch == '.'
+}
+
+fn push_token(
+    tokens: &mut Vec<(Range<usize>, TokenKind)>,
+    text: &str,
+    before: &str,
+    after: &str,
+    kind: TokenKind,
+) {
+    let start = text.len() - before.len();
+    let end = text.len() - after.len();
+    tokens.push((start..end, kind));
 }
 
 impl Op {
     const DEFAULT: Self = Op::Exact;
 }
 
+// Parse the first `||` alternative, in which a wildcard may stand for the whole
+// req, and everything after it. `text` has no leading spaces and is not empty.
+fn parser_first_alternative(text: &str, out: &mut Vec<VersionRange>) -> Result<(), Error> {
+    if let Some((ch, text)) = wildcard(text) {
+        let rest = text.trim_start_matches(' ');
+        if rest.is_empty() {
+            return Ok(());
+        } else if rest.starts_with(',') {
+            return Err(Error::new(ErrorKind::WildcardNotTheOnlyComparator(ch)));
+        } else {
+            return Err(Error::new(ErrorKind::UnexpectedAfterWildcard));
+        }
+    }
+    parser_version_req(text, out)
+}
+
+pub(crate) fn version_req_from_parsed(ranges: Vec<VersionRange>) -> VersionReq {
+    if let [VersionRange::Simple(cmp)] = ranges.as_slice() {
+        if is_greater_eq_zero(cmp) {
+            // Matches the same versions as `*`, which is faster to match.
+            return VersionReq::STAR;
+        }
+    }
+    VersionReq { ranges }
+}
+
+// The endpoints of a hyphen range are plain versions, possibly partial. They
+// are displayed with `=`, and a wildcard is only another way to write a partial
+// version, so those are the only operators accepted on them.
+fn is_hyphen_endpoint(cmp: &Comparator) -> bool {
+    cmp.op == Op::Exact || cmp.op == Op::Wildcard
+}
+
+// Whether the comparator is `>=0.0.0`, `>=0.0`, or `>=0`.
+fn is_greater_eq_zero(cmp: &Comparator) -> bool {
+    cmp.op == Op::GreaterEq
+        && cmp.major == 0
+        && cmp.minor.unwrap_or(0) == 0
+        && cmp.patch.unwrap_or(0) == 0
+        && cmp.pre.is_empty()
+}
+
 fn numeric_identifier(input: &str, pos: Position) -> Result<(u64, &str), Error> {
     let mut len = 0;
     let mut value = 0u64;
@@ -277,7 +908,11 @@ fn op(input: &str) -> (Op, &str) {
             (Op::Less, &input[1..])
         }
     } else if bytes.first() == Some(&b'~') {
-        (Op::Tilde, &input[1..])
+        if bytes.get(1) == Some(&b'>') {
+            (Op::Pessimistic, &input[2..])
+        } else {
+            (Op::Tilde, &input[1..])
+        }
     } else if bytes.first() == Some(&b'^') {
         (Op::Caret, &input[1..])
     } else {
@@ -314,6 +949,7 @@ fn parse_comparator(input: &str) -> Result<(Comparator, Position, &str), Error>
     let (patch, text) = if let Some(text) = text.strip_prefix('.') {
         pos = Position::Patch;
         if let Some((_, text)) = wildcard(text) {
+            has_wildcard = true;
             if default_op {
                 op = Op::Wildcard;
             }
@@ -328,6 +964,29 @@ fn parse_comparator(input: &str) -> Result<(Comparator, Position, &str), Error>
         (None, text)
     };
 
+    // A wildcard must be the last component of the partial version, and
+    // can't run into the next comparator as in `1.2.*3`. A `-` followed by a
+    // digit starts the upper end of a hyphen range, as in `1.x-2.x`, rather
+    // than a pre-release as in `1.2.*-rc`.
+    if has_wildcard
+        && (text.starts_with('.')
+            || text.starts_with('+')
+            || text.starts_with(|ch: char| ch.is_ascii_digit())
+            || (text.starts_with('-') && !text[1..].starts_with(|ch: char| ch.is_ascii_digit())))
+    {
+        return Err(Error::new(ErrorKind::UnexpectedAfterWildcard));
+    }
+
+    // `1.2-rc` is a pre-release on a partial version, which has no meaning,
+    // rather than a hyphen range, whose upper end would start with a digit.
+    if patch.is_none() && !has_wildcard && text.starts_with('-') {
+        if let Some(ch) = text[1..].chars().next() {
+            if ch.is_ascii_alphabetic() {
+                return Err(Error::new(ErrorKind::PrereleaseOnPartial(pos)));
+            }
+        }
+    }
+
     let (pre, text) = if patch.is_some() && text.starts_with('-') {
         pos = Position::Pre;
         let text = &text[1..];
@@ -380,6 +1039,9 @@ fn parser_version_req(text: &str, out: &mut Vec<VersionRange>) -> Result<(), Err
         return Ok(());
     } else if let Some(text) = text.strip_prefix('-') {
         let (right, _pos, text) = parse_comparator(text)?;
+        if !is_hyphen_endpoint(&comparator) || !is_hyphen_endpoint(&right) {
+            return Err(Error::new(ErrorKind::OperatorInHyphenRange));
+        }
         out.push(VersionRange::Hyphen(comparator, right));
         if text.is_empty() {
             return Ok(());
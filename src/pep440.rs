@@ -0,0 +1,69 @@
+use crate::{Error, Version};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+/// A version with a Python [PEP 440] style epoch in front, `epoch!version`,
+/// such as `1!2.0.0`.
+///
+/// [PEP 440]: https://peps.python.org/pep-0440/
+///
+/// The epoch lets a project reset its version numbering: any version of a
+/// greater epoch is greater, regardless of the version that follows, so
+/// `1!1.0.0` is greater than `0!2.0.0`. Versions of the same epoch are
+/// ordered by [`Version`]'s `Ord`. A version without an epoch has epoch 0.
+///
+/// Only the epoch is borrowed from PEP 440; the rest is a SemVer version,
+/// with SemVer's syntax for pre-releases and build metadata.
+///
+/// An epoch of 0 is left out when displayed, as in the normalized form of PEP
+/// 440. Converting to a [`Version`] drops the epoch.
+///
+/// ```
+/// use semver::{PepVersion, Version};
+///
+/// let pep = PepVersion::parse("1!1.2.3").unwrap();
+/// assert_eq!(pep.epoch, 1);
+/// assert!(pep > PepVersion::parse("9.0.0").unwrap());
+/// assert_eq!(Version::from(pep), Version::new(1, 2, 3));
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct PepVersion {
+    pub epoch: u64,
+    pub version: Version,
+}
+
+impl PepVersion {
+    pub const fn new(epoch: u64, version: Version) -> Self {
+        PepVersion { epoch, version }
+    }
+
+    /// Create `PepVersion` by parsing from string representation.
+    ///
+    /// # Errors
+    ///
+    /// Possible reasons for the parse to fail include:
+    ///
+    /// - `!1.2.3` or `01!1.2.3` &mdash; an empty epoch, or one with a leading
+    ///   zero, as for the numbers of [`Version::parse`].
+    ///
+    /// - `1!1.2` &mdash; any of the errors of [`Version::parse`] in the version
+    ///   after the epoch.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        PepVersion::from_str(text)
+    }
+}
+
+impl From<PepVersion> for Version {
+    fn from(pep: PepVersion) -> Self {
+        pep.version
+    }
+}
+
+impl Display for PepVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(formatter, "{}!", self.epoch)?;
+        }
+        write!(formatter, "{}", self.version)
+    }
+}
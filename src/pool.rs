@@ -0,0 +1,91 @@
+use crate::alloc::collections::BTreeMap;
+use crate::alloc::vec::Vec;
+use crate::Version;
+use core::u32;
+
+/// Deduplicating storage for [`Version`] values.
+///
+/// Interning a version hands back a [`VersionHandle`], a 4-byte `Copy` token
+/// which can be turned back into a reference to the version using
+/// [`VersionPool::get`]. Versions that are equal, meaning equal precedence and
+/// equal build metadata, are stored only once no matter how many times they
+/// are interned.
+///
+/// # Example
+///
+/// ```
+/// use semver::{Version, VersionPool};
+///
+/// let mut pool = VersionPool::new();
+/// let a = pool.intern(Version::parse("1.0.0").unwrap());
+/// let b = pool.intern(Version::parse("1.0.0").unwrap());
+/// let c = pool.intern(Version::parse("1.0.0+build").unwrap());
+///
+/// assert_eq!(a, b);
+/// assert_ne!(a, c);
+/// assert_eq!(pool.len(), 2);
+/// assert_eq!(pool.get(a).to_string(), "1.0.0");
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct VersionPool {
+    versions: Vec<Version>,
+    index: BTreeMap<Version, u32>,
+}
+
+/// Handle to a version interned in a [`VersionPool`].
+///
+/// Handles compare equal if and only if they refer to equal versions of the
+/// same pool. The order of handles is the order in which versions were first
+/// interned, not the order of the versions themselves.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct VersionHandle(u32);
+
+impl VersionPool {
+    pub fn new() -> Self {
+        VersionPool {
+            versions: Vec::new(),
+            index: BTreeMap::new(),
+        }
+    }
+
+    /// Store `version` in the pool, unless an equal version is already
+    /// present, and return its handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool would contain more than u32::MAX distinct versions.
+    pub fn intern(&mut self, version: Version) -> VersionHandle {
+        if let Some(&index) = self.index.get(&version) {
+            return VersionHandle(index);
+        }
+        let index = self.versions.len();
+        assert!(
+            index < u32::MAX as usize,
+            "too many versions in VersionPool"
+        );
+        let index = index as u32;
+        self.versions.push(version.clone());
+        self.index.insert(version, index);
+        VersionHandle(index)
+    }
+
+    /// The version behind a handle previously returned by this pool's
+    /// [`intern`][VersionPool::intern].
+    ///
+    /// # Panics
+    ///
+    /// May panic, or return an unrelated version, if `handle` came from a
+    /// different pool.
+    pub fn get(&self, handle: VersionHandle) -> &Version {
+        &self.versions[handle.0 as usize]
+    }
+
+    /// Number of distinct versions in the pool.
+    pub fn len(&self) -> usize {
+        self.versions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.versions.is_empty()
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Error, Version};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+/// A version with four numeric components, `major.minor.patch.revision`, as
+/// used by .NET assemblies and Windows file versions, such as `10.0.19041.1`.
+///
+/// This is not SemVer: there is no pre-release or build metadata, and each
+/// of the four numbers is compared in turn, so the revision is the component
+/// of lowest precedence. `1.2.3.4` is less than `1.2.3.10`, which is less
+/// than `1.2.4.0`.
+///
+/// Converting to a [`Version`] drops the revision.
+///
+/// ```
+/// use semver::{QuadVersion, Version};
+///
+/// let quad = QuadVersion::parse("1.2.3.4").unwrap();
+/// assert_eq!(quad.revision, 4);
+/// assert!(quad < QuadVersion::new(1, 2, 3, 10));
+/// assert_eq!(Version::from(quad), Version::new(1, 2, 3));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct QuadVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub revision: u64,
+}
+
+impl QuadVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64, revision: u64) -> Self {
+        QuadVersion {
+            major,
+            minor,
+            patch,
+            revision,
+        }
+    }
+
+    /// Create `QuadVersion` by parsing from string representation.
+    ///
+    /// # Errors
+    ///
+    /// Possible reasons for the parse to fail include:
+    ///
+    /// - `1.2.3` &mdash; fewer than four components.
+    ///
+    /// - `1.2.3.04` &mdash; a leading zero in any of the four numbers, as in
+    ///   [`Version::parse`].
+    ///
+    /// - `1.2.3.4-rc.1` &mdash; anything after the revision number.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        QuadVersion::from_str(text)
+    }
+}
+
+impl From<QuadVersion> for Version {
+    fn from(quad: QuadVersion) -> Self {
+        Version::new(quad.major, quad.minor, quad.patch)
+    }
+}
+
+impl Display for QuadVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.revision,
+        )
+    }
+}
@@ -1,6 +1,7 @@
+use crate::alloc::vec::Vec;
 use crate::{Comparator, Version, VersionReq};
 use core::fmt;
-use serde::de::{Deserialize, Deserializer, Error, Visitor};
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
 
 impl Serialize for Version {
@@ -107,3 +108,59 @@ impl<'de> Deserialize<'de> for Comparator {
         deserializer.deserialize_str(ComparatorVisitor)
     }
 }
+
+impl VersionReq {
+    /// Deserialize either a single requirement string, or an array of them
+    /// that are combined with [`VersionReq::intersect_all`], so that
+    /// `["^1.2", ">=1.4 <3"]` means the same as `^1.2 >=1.4 <3`.
+    ///
+    /// This is meant for `#[serde(deserialize_with =
+    /// "VersionReq::deserialize_all_of")]` on a field. The usual `Deserialize`
+    /// impl of `VersionReq` accepts only a string, so that it keeps working
+    /// with formats that are not self-describing.
+    ///
+    /// ```
+    /// use semver::{Version, VersionReq};
+    /// use serde::de::value::{Error, SeqDeserializer};
+    ///
+    /// let array = SeqDeserializer::<_, Error>::new(vec!["^1.2", ">=1.4 <3"].into_iter());
+    /// let req = VersionReq::deserialize_all_of(array).unwrap();
+    /// assert!(req.matches(&Version::parse("1.5.0").unwrap()));
+    /// assert!(!req.matches(&Version::parse("1.3.0").unwrap()));
+    /// ```
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn deserialize_all_of<'de, D>(deserializer: D) -> Result<VersionReq, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct AllOfVisitor;
+
+        impl<'de> Visitor<'de> for AllOfVisitor {
+            type Value = VersionReq;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("semver version req or array of them")
+            }
+
+            fn visit_str<E>(self, string: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                string.parse().map_err(Error::custom)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut reqs = Vec::new();
+                while let Some(req) = seq.next_element::<VersionReq>()? {
+                    reqs.push(req);
+                }
+                Ok(VersionReq::intersect_all(reqs))
+            }
+        }
+
+        deserializer.deserialize_any(AllOfVisitor)
+    }
+}
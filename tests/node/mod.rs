@@ -2,6 +2,7 @@
 
 use semver::Version;
 use std::fmt::{self, Display};
+use std::ops::Deref;
 use std::process::Command;
 
 #[derive(Default, Eq, PartialEq, Hash, Debug)]
@@ -31,6 +32,15 @@ impl VersionReq {
     }
 }
 
+// Methods other than `matches` are not checked against node-semver.
+impl Deref for VersionReq {
+    type Target = semver::VersionReq;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 impl Display for VersionReq {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         Display::fmt(&self.0, formatter)
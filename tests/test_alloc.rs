@@ -0,0 +1,48 @@
+// Counts the heap allocations made while parsing versions. This is the only
+// test in this binary, so that no other test allocates while it is counting.
+
+use semver::Version;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counter;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counter = Counter;
+
+fn allocations(text: &str) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let version = Version::parse(text).unwrap();
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+    drop(version);
+    after - before
+}
+
+#[test]
+fn test_parse_allocations() {
+    // Empty pre-release and build metadata are not allocated, nor are
+    // identifiers of up to 8 bytes, which are stored inline.
+    assert_eq!(allocations("1.2.3"), 0);
+    assert_eq!(allocations("18446744073709551615.0.0"), 0);
+    assert_eq!(allocations("1.2.3-rc.1"), 0);
+    assert_eq!(allocations("1.2.3-rc.1+build.5"), 0);
+    assert_eq!(allocations("1.2.3-alpha.12"), 0);
+
+    // Longer identifiers take one allocation each.
+    assert_eq!(allocations("1.2.3-alpha.123"), 1);
+    assert_eq!(allocations("1.2.3+build.20241014"), 1);
+    assert_eq!(allocations("1.2.3-alpha.beta.1+build.20241014"), 2);
+}
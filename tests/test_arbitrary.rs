@@ -0,0 +1,60 @@
+#![cfg(feature = "arbitrary")]
+
+mod util;
+
+use crate::util::Xorshift;
+use arbitrary::{Arbitrary, Unstructured};
+use semver::{Version, VersionRange, VersionReq};
+
+fn inputs() -> impl Iterator<Item = Vec<u8>> {
+    let mut random = Xorshift::new();
+    (0..2000).map(move |_| (0..256).map(|_| random.next_u64() as u8).collect())
+}
+
+#[test]
+fn test_version_round_trip() {
+    let mut with_pre = 0;
+    let mut with_build = 0;
+    for data in inputs() {
+        let mut u = Unstructured::new(&data);
+        let version = Version::arbitrary(&mut u).unwrap();
+        let text = version.to_string();
+        assert_eq!(Version::parse(&text).unwrap(), version, "{}", text);
+        with_pre += !version.pre.is_empty() as usize;
+        with_build += !version.build.is_empty() as usize;
+    }
+    assert!(with_pre > 100);
+    assert!(with_build > 100);
+}
+
+#[test]
+fn test_version_req_round_trip() {
+    let mut star = 0;
+    let mut hyphen = 0;
+    let mut intersection = 0;
+    for data in inputs() {
+        let mut u = Unstructured::new(&data);
+        let req = VersionReq::arbitrary(&mut u).unwrap();
+        let text = req.to_string();
+        assert_eq!(VersionReq::parse(&text).unwrap(), req, "{}", text);
+        star += req.ranges.is_empty() as usize;
+        for range in &req.ranges {
+            match range {
+                VersionRange::Hyphen(..) => hyphen += 1,
+                VersionRange::Intersection(..) => intersection += 1,
+                _ => {}
+            }
+        }
+    }
+    assert!(star > 100);
+    assert!(hyphen > 100);
+    assert!(intersection > 100);
+}
+
+#[test]
+fn test_exhausted_input() {
+    // Running out of input yields defaults rather than an error.
+    let mut u = Unstructured::new(&[]);
+    assert_eq!(Version::arbitrary(&mut u).unwrap(), Version::new(0, 0, 0));
+    assert_eq!(VersionReq::arbitrary(&mut u).unwrap(), VersionReq::STAR);
+}
@@ -11,4 +11,17 @@ fn test() {
     assert_send_sync::<semver::Version>();
     assert_send_sync::<semver::VersionReq>();
     assert_send_sync::<semver::Op>();
+    assert_send_sync::<semver::VersionHandle>();
+    assert_send_sync::<semver::VersionPool>();
+    assert_send_sync::<semver::TokenKind>();
+    assert_send_sync::<semver::PartialVersion>();
+    assert_send_sync::<semver::TotalVersion>();
+    assert_send_sync::<semver::VersionReqParser>();
+    assert_send_sync::<semver::PaddedVersion>();
+    assert_send_sync::<semver::MatchCache>();
+    assert_send_sync::<semver::IncludeBoundsMode>();
+    assert_send_sync::<semver::GitDescribe>();
+    assert_send_sync::<semver::VersionBuilder>();
+    assert_send_sync::<semver::MatchReason>();
+    assert_send_sync::<semver::CompiledReq>();
 }
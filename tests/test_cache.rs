@@ -0,0 +1,46 @@
+#![allow(clippy::wildcard_imports)]
+
+mod util;
+
+use crate::util::*;
+use semver::{MatchCache, VersionReq};
+
+#[test]
+fn test_matches_cached() {
+    let reqs = [
+        "*",
+        "^1.2.3",
+        "1.2.3 - 1.4.0 || ^2.1 || >=3.0.0-rc.1 <3.1",
+        ">1.2.3-alpha",
+        "<0.0.0",
+    ];
+    let versions = [
+        "0.0.0",
+        "1.2.3",
+        "1.2.3-alpha.1",
+        "1.2.3+build",
+        "1.3.0",
+        "2.1.7",
+        "2.1.7-rc.1",
+        "3.0.0-rc.2",
+        "3.0.0-rc.2+build",
+        "3.0.5",
+    ];
+
+    for text in &reqs {
+        let r = VersionReq::parse(text).unwrap();
+        let mut cache = MatchCache::new(r.clone());
+        assert!(cache.is_empty());
+        for _ in 0..3 {
+            for v in &versions {
+                let v = version(v);
+                assert_eq!(cache.matches(&v), r.matches(&v), "{} {}", text, v);
+            }
+        }
+        assert_eq!(cache.len(), versions.len());
+        assert_eq!(*cache.req(), r);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+#![allow(clippy::wildcard_imports)]
+
+mod util;
+
+use crate::util::*;
+use semver::{CompiledReq, Version, VersionReq};
+
+fn assert_same(text: &str, versions: &[Version]) {
+    let r = VersionReq::parse(text).unwrap();
+    let compiled = CompiledReq::new(r.clone());
+    assert_eq!(*compiled.req(), r);
+    for v in versions {
+        assert_eq!(compiled.matches(v), r.matches(v), "{} {}", text, v);
+    }
+}
+
+#[test]
+fn test_compiled_comparators() {
+    let versions = small_versions();
+    for text in &small_comparators() {
+        assert_same(text, &versions);
+    }
+    for text in &["*", "<0.0.0", ">=0.0.0-0", "1.2.3 - 2", "1 - 1.2.0-rc.1"] {
+        assert_same(text, &versions);
+    }
+}
+
+#[test]
+fn test_compiled_combinations() {
+    let comparators = small_comparators();
+    let versions = small_versions();
+
+    let mut random = Xorshift::new();
+    let mut choose = || random.choose(&comparators).as_str();
+
+    for _ in 0..2000 {
+        let intersection = format!("{} {}", choose(), choose());
+        assert_same(&intersection, &versions);
+        let union = format!("{} || {} || {}", choose(), choose(), choose());
+        assert_same(&union, &versions);
+        let mixed = format!("{} {} || {}", choose(), choose(), choose());
+        assert_same(&mixed, &versions);
+    }
+}
@@ -8,7 +8,7 @@
 mod util;
 
 use crate::util::*;
-use semver::Prerelease;
+use semver::{BuildMetadata, Prerelease};
 
 #[test]
 fn test_new() {
@@ -43,3 +43,34 @@ fn test_eq() {
     assert_ne!(prerelease("aaaaaaaaa"), prerelease("bbbbbbbbb"));
     assert_ne!(build_metadata("1"), build_metadata("001"));
 }
+
+#[test]
+fn test_display() {
+    assert_to_string(prerelease("alpha.3"), "alpha.3");
+    assert_to_string(prerelease(""), "");
+    assert_to_string(Prerelease::EMPTY, "");
+    assert_to_string(prerelease("0.a-b.long-identifier"), "0.a-b.long-identifier");
+    assert_to_string(build_metadata("sha.5114f85"), "sha.5114f85");
+    assert_to_string(build_metadata(""), "");
+    assert_to_string(BuildMetadata::EMPTY, "");
+
+    let ver = version("1.2.3-alpha.3+sha.5114f85");
+    assert_to_string(&ver.pre, "alpha.3");
+    assert_to_string(&ver.build, "sha.5114f85");
+    let custom = format!("{}.{}-{}", ver.major, ver.minor, ver.pre);
+    assert_eq!(custom, "1.2-alpha.3");
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(
+        format!("{:?}", prerelease("alpha.3")),
+        "Prerelease(\"alpha.3\")"
+    );
+    assert_eq!(format!("{:?}", Prerelease::EMPTY), "Prerelease(\"\")");
+    assert_eq!(
+        format!("{:?}", build_metadata("sha.5114f85")),
+        "BuildMetadata(\"sha.5114f85\")",
+    );
+    assert_eq!(format!("{:?}", BuildMetadata::EMPTY), "BuildMetadata(\"\")");
+}
@@ -0,0 +1,45 @@
+use semver::{Version, VersionReq};
+
+// Generated by running node-semver's `satisfies` over every combination of a
+// list of reqs and a list of versions.
+const FIXTURE: &str = include_str!("fixture/node-semver.tsv");
+
+// Pairs on which this crate intentionally disagrees with node-semver.
+const DEVIATIONS: &[(&str, &str)] = &[
+    // `~>` is the pessimistic operator, which for a major.minor version allows
+    // any later minor version. node-semver parses `~>` as a synonym of `~`.
+    ("~>1.2", "1.3.0"),
+    ("~>1.2", "1.9.9"),
+];
+
+#[test]
+fn test_node_fixture() {
+    let mut mismatches = Vec::new();
+    let mut deviations = Vec::new();
+
+    for line in FIXTURE.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let (text, version, expected) = (fields[0], fields[1], fields[2] == "true");
+
+        let req = VersionReq::parse(text).unwrap();
+        let version = Version::parse(version).unwrap();
+        if req.matches(&version) == expected {
+            continue;
+        }
+
+        if DEVIATIONS.contains(&(fields[0], fields[1])) {
+            deviations.push((fields[0], fields[1]));
+        } else {
+            mismatches.push(format!(
+                "{} {} node-semver={}",
+                fields[0], fields[1], expected,
+            ));
+        }
+    }
+
+    assert!(mismatches.is_empty(), "{:#?}", mismatches);
+    assert_eq!(deviations, DEVIATIONS);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "pep440")]
+
+mod util;
+
+use crate::util::*;
+use semver::{PepVersion, Version};
+
+fn pep(text: &str) -> PepVersion {
+    PepVersion::parse(text).unwrap()
+}
+
+fn pep_err(text: &str) -> semver::Error {
+    PepVersion::parse(text).unwrap_err()
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(pep("1!1.2.3"), PepVersion::new(1, version("1.2.3")));
+    assert_eq!(pep("1.2.3"), PepVersion::new(0, version("1.2.3")));
+    assert_eq!(pep("0!1.2.3"), pep("1.2.3"));
+    assert_eq!(
+        pep("2!1.0.0-rc.1+build"),
+        PepVersion::new(2, version("1.0.0-rc.1+build")),
+    );
+
+    let err = pep_err("");
+    assert_to_string(err, "empty string, expected a semver version");
+
+    let err = pep_err("!1.2.3");
+    assert_to_string(err, "unexpected end of input while parsing epoch");
+
+    let err = pep_err("01!1.2.3");
+    assert_to_string(err, "invalid leading zero in epoch");
+
+    let err = pep_err("1a!1.2.3");
+    assert_to_string(err, "unexpected character 'a' after epoch");
+
+    let err = pep_err("18446744073709551616!1.2.3");
+    assert_to_string(err, "value of epoch exceeds u64::MAX");
+
+    let err = pep_err("1!");
+    assert_to_string(err, "empty string, expected a semver version");
+
+    let err = pep_err("1!1.2");
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing minor version number",
+    );
+
+    let err = pep_err("1!2!1.2.3");
+    assert_to_string(err, "unexpected character '!' after major version number");
+}
+
+#[test]
+fn test_display() {
+    assert_to_string(pep("1!1.2.3"), "1!1.2.3");
+    assert_to_string(pep("1.2.3-rc.1"), "1.2.3-rc.1");
+    assert_to_string(pep("0!1.2.3"), "1.2.3");
+}
+
+#[test]
+fn test_ord() {
+    // A higher epoch beats any version.
+    assert!(pep("1!1.0.0") > pep("2.0.0"));
+    assert!(pep("1!0.0.0") > pep("0!999.999.999"));
+    assert!(pep("2!1.0.0-rc.1") > pep("1!9.0.0"));
+
+    // Versions of the same epoch compare as usual.
+    assert!(pep("1!1.2.3") < pep("1!1.2.4"));
+    assert!(pep("1!1.2.3-rc.1") < pep("1!1.2.3"));
+
+    let mut versions = vec![pep("3.0.0"), pep("1!1.0.0"), pep("2!0.1.0"), pep("1.0.0")];
+    versions.sort();
+    assert_eq!(
+        versions,
+        [pep("1.0.0"), pep("3.0.0"), pep("1!1.0.0"), pep("2!0.1.0")],
+    );
+}
+
+#[test]
+fn test_into_version() {
+    assert_eq!(Version::from(pep("1!1.2.3")), version("1.2.3"));
+    assert_eq!(Version::from(pep("1!1.2.3")), Version::from(pep("2!1.2.3")));
+}
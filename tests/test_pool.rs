@@ -0,0 +1,59 @@
+#![allow(clippy::wildcard_imports)]
+
+mod util;
+
+use crate::util::*;
+use semver::{Version, VersionPool};
+use std::mem;
+
+#[test]
+fn test_intern() {
+    let mut pool = VersionPool::new();
+    assert!(pool.is_empty());
+
+    let a = pool.intern(version("1.2.3"));
+    let b = pool.intern(version("1.2.3-rc.1"));
+    let c = pool.intern(version("1.2.3+build"));
+    let d = pool.intern(version("1.2.3"));
+    let e = pool.intern(version("1.2.3+build"));
+
+    assert_eq!(a, d);
+    assert_eq!(c, e);
+    assert_ne!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(pool.len(), 3);
+
+    assert_eq!(*pool.get(a), version("1.2.3"));
+    assert_eq!(*pool.get(b), version("1.2.3-rc.1"));
+    assert_eq!(*pool.get(c), version("1.2.3+build"));
+}
+
+#[test]
+fn test_build_metadata_distinct() {
+    // Numerically equal build metadata is still distinct build metadata.
+    let mut pool = VersionPool::new();
+    let a = pool.intern(version("1.0.0+1"));
+    let b = pool.intern(version("1.0.0+001"));
+    assert_ne!(a, b);
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn test_memory() {
+    let mut versions = Vec::new();
+    for i in 0..10_000u64 {
+        versions.push(Version::new(1, i % 10, i % 7));
+    }
+
+    let mut pool = VersionPool::new();
+    let handles: Vec<_> = versions.iter().cloned().map(|v| pool.intern(v)).collect();
+    assert_eq!(pool.len(), 70);
+    for (handle, version) in handles.iter().zip(&versions) {
+        assert_eq!(pool.get(*handle), version);
+    }
+
+    let unpooled = versions.len() * mem::size_of::<Version>();
+    let pooled =
+        handles.len() * mem::size_of_val(&handles[0]) + pool.len() * 2 * mem::size_of::<Version>();
+    assert!(pooled * 5 < unpooled, "{} vs {}", pooled, unpooled);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "four_component")]
+
+mod util;
+
+use crate::util::*;
+use semver::{QuadVersion, Version};
+
+fn quad(text: &str) -> QuadVersion {
+    QuadVersion::parse(text).unwrap()
+}
+
+fn quad_err(text: &str) -> semver::Error {
+    QuadVersion::parse(text).unwrap_err()
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(quad("1.2.3.4"), QuadVersion::new(1, 2, 3, 4));
+    assert_eq!(quad("0.0.0.0"), QuadVersion::new(0, 0, 0, 0));
+    assert_eq!(
+        quad("10.0.19041.1288"),
+        QuadVersion::new(10, 0, 19041, 1288)
+    );
+
+    let err = quad_err("");
+    assert_to_string(err, "empty string, expected a semver version");
+
+    let err = quad_err("1.2.3");
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing patch version number",
+    );
+
+    let err = quad_err("1.2.3.");
+    assert_to_string(err, "unexpected end of input while parsing revision number");
+
+    let err = quad_err("1.2.3.04");
+    assert_to_string(err, "invalid leading zero in revision number");
+
+    let err = quad_err("1.2.3.4.5");
+    assert_to_string(err, "unexpected character '.' after revision number");
+
+    let err = quad_err("1.2.3.4-rc.1");
+    assert_to_string(err, "unexpected character '-' after revision number");
+
+    let err = quad_err("1.2.3.18446744073709551616");
+    assert_to_string(err, "value of revision number exceeds u64::MAX");
+}
+
+#[test]
+fn test_display() {
+    assert_to_string(quad("1.2.3.4"), "1.2.3.4");
+    assert_to_string(QuadVersion::new(0, 0, 0, 0), "0.0.0.0");
+}
+
+#[test]
+fn test_ord() {
+    assert!(quad("1.2.3.4") < quad("1.2.3.10"));
+    assert!(quad("1.2.3.10") < quad("1.2.4.0"));
+    assert!(quad("1.2.3.99") < quad("1.3.0.0"));
+    assert!(quad("1.9.9.9") < quad("2.0.0.0"));
+    assert_eq!(quad("1.2.3.4"), QuadVersion::new(1, 2, 3, 4));
+
+    // Revision breaks ties.
+    let mut versions = vec![
+        quad("1.2.3.2"),
+        quad("1.2.3.10"),
+        quad("1.2.3.0"),
+        quad("1.2.2.7"),
+    ];
+    versions.sort();
+    assert_eq!(
+        versions,
+        [
+            quad("1.2.2.7"),
+            quad("1.2.3.0"),
+            quad("1.2.3.2"),
+            quad("1.2.3.10")
+        ],
+    );
+}
+
+#[test]
+fn test_into_version() {
+    assert_eq!(Version::from(quad("1.2.3.4")), version("1.2.3"));
+    assert_eq!(
+        Version::from(quad("1.2.3.0")),
+        Version::from(quad("1.2.3.9"))
+    );
+}
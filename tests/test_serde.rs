@@ -0,0 +1,258 @@
+#![cfg(feature = "serde")]
+#![allow(clippy::toplevel_ref_arg, clippy::wildcard_imports)]
+
+mod util;
+
+use crate::util::*;
+use semver::{Version, VersionReq};
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer, StrDeserializer};
+use serde::de::{Deserialize, Deserializer, IntoDeserializer, MapAccess, Visitor};
+use serde::ser::{self, Impossible, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+fn from_str(text: &str) -> Result<VersionReq, Error> {
+    let deserializer: StrDeserializer<Error> = text.into_deserializer();
+    VersionReq::deserialize_all_of(deserializer)
+}
+
+fn from_array(texts: &[&str]) -> Result<VersionReq, Error> {
+    let deserializer = SeqDeserializer::<_, Error>::new(texts.iter().cloned());
+    VersionReq::deserialize_all_of(deserializer)
+}
+
+#[test]
+fn test_deserialize() {
+    let deserializer: StrDeserializer<Error> = "^1.2".into_deserializer();
+    assert_eq!(VersionReq::deserialize(deserializer).unwrap(), req("^1.2"));
+
+    let deserializer = SeqDeserializer::<_, Error>::new(vec!["^1.2"].into_iter());
+    assert!(VersionReq::deserialize(deserializer).is_err());
+}
+
+#[test]
+fn test_deserialize_all_of() {
+    let ref r = from_str("^1.2").unwrap();
+    assert_eq!(*r, req("^1.2"));
+
+    let ref r = from_array(&["^1.2", ">=2.0 <3.0"]).unwrap();
+    assert!(!r.matches(&version("1.2.0")));
+    assert!(!r.matches(&version("2.0.0")));
+    assert!(!r.matches(&version("0.9.0")));
+
+    let ref r = from_array(&["^1.2", ">=1.4 <3"]).unwrap();
+    assert_to_string(r, "^1.2 >=1.4 <3");
+    assert!(r.matches(&version("1.4.0")));
+    assert!(r.matches(&version("1.9.9")));
+    assert!(!r.matches(&version("1.2.0")));
+    assert!(!r.matches(&version("1.3.9")));
+    assert!(!r.matches(&version("2.0.0")));
+    assert!(!r.matches(&version("3.0.0")));
+
+    let ref r = from_array(&["^1.2 || ^2", "<2.5", "*"]).unwrap();
+    assert_to_string(r, "^1.2 <2.5 || ^2 <2.5");
+    assert!(r.matches(&version("1.2.0")));
+    assert!(r.matches(&version("2.4.9")));
+    assert!(!r.matches(&version("1.1.0")));
+    assert!(!r.matches(&version("2.5.0")));
+
+    let ref r = from_array(&["1.0.0 - 2.0.0", "*"]).unwrap();
+    assert_to_string(r, ">=1.0.0 <=2.0.0");
+    assert!(r.matches(&version("1.0.0")));
+    assert!(r.matches(&version("2.0.0")));
+
+    let ref r = from_array(&[]).unwrap();
+    assert_eq!(*r, VersionReq::STAR);
+
+    assert!(from_array(&["^1.2", "^1.q"]).is_err());
+}
+
+// Serializes a map key the way JSON does: only strings are accepted, so a
+// `HashMap<Version, T>` can be written as an object keyed by version strings.
+struct MapKeySerializer;
+
+macro_rules! reject {
+    ($($method:ident($($arg:ty),*)),*) => {
+        $(
+            fn $method(self $(, _: $arg)*) -> Result<String, Error> {
+                Err(ser::Error::custom("map key must be a string"))
+            }
+        )*
+    };
+}
+
+impl Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_str(self, value: &str) -> Result<String, Error> {
+        Ok(value.to_owned())
+    }
+
+    fn collect_str<T>(self, value: &T) -> Result<String, Error>
+    where
+        T: ?Sized + Display,
+    {
+        Ok(value.to_string())
+    }
+
+    reject! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<String, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(ser::Error::custom("map key must be a string"))
+    }
+}
+
+// The entries of a map, in order, as deserialized from a map whose keys are
+// versions.
+struct Entries(Vec<(Version, u32)>);
+
+impl<'de> Deserialize<'de> for Entries {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = Entries;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("map of versions")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Entries, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+#[test]
+fn test_version_map_key() {
+    let mut map = HashMap::new();
+    map.insert(version("1.2.3"), 1);
+    map.insert(version("1.3.0-rc.1+build.5"), 2);
+    map.insert(version("0.0.1"), 3);
+
+    let mut keys = Vec::new();
+    for (key, value) in &map {
+        keys.push((key.serialize(MapKeySerializer).unwrap(), *value));
+    }
+    keys.sort_by_key(|&(_, value)| value);
+    assert_eq!(
+        keys,
+        [
+            ("1.2.3".to_owned(), 1),
+            ("1.3.0-rc.1+build.5".to_owned(), 2),
+            ("0.0.1".to_owned(), 3),
+        ],
+    );
+
+    // Keys are handed to the visitor as strings, as JSON object keys are.
+    let entries = keys.iter().map(|(key, value)| (key.as_str(), *value));
+    let deserializer = MapDeserializer::<_, Error>::new(entries);
+    let Entries(entries) = Entries::deserialize(deserializer).unwrap();
+    let round_trip = entries.into_iter().collect::<HashMap<_, _>>();
+    assert_eq!(round_trip, map);
+
+    let entries = vec![("1.2", 1)].into_iter();
+    let deserializer = MapDeserializer::<_, Error>::new(entries);
+    assert!(Entries::deserialize(deserializer).is_err());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "trace")]
+
+mod util;
+
+use crate::util::*;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::cell::RefCell;
+
+thread_local! {
+    static MESSAGES: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+struct Capture;
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Trace && metadata.target().starts_with("semver")
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let message = record.args().to_string();
+            MESSAGES.with(|messages| messages.borrow_mut().push(message));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Capture = Capture;
+
+fn capture(f: impl FnOnce()) -> Vec<String> {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(LevelFilter::Trace);
+    MESSAGES.with(|messages| messages.borrow_mut().clear());
+    f();
+    MESSAGES.with(|messages| messages.borrow_mut().split_off(0))
+}
+
+#[test]
+fn test_trace_matches() {
+    let messages = capture(|| assert!(req("^1.2.3").matches(&version("1.5.0"))));
+    assert_eq!(messages, ["^1.2.3 vs 1.5.0: matches_caret=true"]);
+
+    let messages = capture(|| assert!(!req(">=1.0.0 <1.5.0").matches(&version("1.5.0"))));
+    assert_eq!(
+        messages,
+        [
+            ">=1.0.0 vs 1.5.0: matches_greater_eq=true",
+            "<1.5.0 vs 1.5.0: matches_less=false",
+        ],
+    );
+
+    let messages = capture(|| assert!(req("1.0.0 - 2.0.0 || ~3").matches(&version("3.1.0"))));
+    assert_eq!(
+        messages,
+        [
+            "=1.0.0 - =2.0.0 vs 3.1.0: matches_hyphen=false",
+            "~3 vs 3.1.0: matches_tilde=true",
+        ],
+    );
+}
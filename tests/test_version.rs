@@ -7,7 +7,14 @@
 mod util;
 
 use crate::util::*;
-use semver::{BuildMetadata, Prerelease, Version};
+use semver::{
+    BuildMetadata, GitDescribe, PartialVersion, Prerelease, TotalVersion, Version, VersionBuilder,
+    VersionReq,
+};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::mem;
+use std::u64;
 
 #[test]
 fn test_parse() {
@@ -236,3 +243,1531 @@ fn test_align() {
     assert_eq!("*****1.2.3-rc1******", format!("{:*^20}", version));
     assert_eq!("           1.2.3-rc1", format!("{:>20}", version));
 }
+
+#[test]
+fn test_next_breaking() {
+    assert_eq!(version("1.2.3").next_breaking(), version("2.0.0"));
+    assert_eq!(version("3.0.0").next_breaking(), version("4.0.0"));
+    assert_eq!(version("1.2.3-rc+b").next_breaking(), version("2.0.0"));
+
+    assert_eq!(version("0.2.3").next_breaking(), version("0.3.0"));
+    assert_eq!(version("0.1.0").next_breaking(), version("0.2.0"));
+    assert_eq!(version("0.1.0-alpha").next_breaking(), version("0.2.0"));
+
+    assert_eq!(version("0.0.3").next_breaking(), version("0.0.4"));
+    assert_eq!(version("0.0.0").next_breaking(), version("0.0.1"));
+    assert_eq!(version("0.0.3+meta").next_breaking(), version("0.0.4"));
+}
+
+#[test]
+#[should_panic(expected = "version number overflow")]
+fn test_next_breaking_major_overflow() {
+    let _ = Version::new(u64::MAX, 0, 0).next_breaking();
+}
+
+#[test]
+#[should_panic(expected = "version number overflow")]
+fn test_next_breaking_minor_overflow() {
+    let _ = Version::new(0, u64::MAX, 7).next_breaking();
+}
+
+#[test]
+#[should_panic(expected = "version number overflow")]
+fn test_next_breaking_patch_overflow() {
+    let _ = Version::new(0, 0, u64::MAX).next_breaking();
+}
+
+#[test]
+fn test_builder() {
+    let v = VersionBuilder::new()
+        .major(1)
+        .minor(2)
+        .patch(3)
+        .pre("rc.1")
+        .build("exp.5")
+        .build_version()
+        .unwrap();
+    assert_eq!(v, version("1.2.3-rc.1+exp.5"));
+    assert_to_string(&v, "1.2.3-rc.1+exp.5");
+
+    let v = VersionBuilder::new().build_version().unwrap();
+    assert_eq!(v, Version::new(0, 0, 0));
+
+    let err = VersionBuilder::new()
+        .major(1)
+        .pre("01")
+        .build_version()
+        .unwrap_err();
+    assert_to_string(err, "invalid leading zero in pre-release identifier");
+
+    // The first error is kept, even if a later setter succeeds.
+    let err = VersionBuilder::new()
+        .pre("rc..1")
+        .build("exp_5")
+        .pre("rc.1")
+        .build_version()
+        .unwrap_err();
+    assert_to_string(err, "empty identifier segment in pre-release identifier");
+}
+
+#[test]
+fn test_bump() {
+    let v = &version("1.2.3-rc.1+build");
+    assert_eq!(v.bump_major(), version("2.0.0"));
+    assert_eq!(v.bump_minor(), version("1.3.0"));
+    assert_eq!(v.bump_patch(), version("1.2.4"));
+    assert_eq!(v.checked_bump_major(), Some(version("2.0.0")));
+    assert_eq!(v.checked_bump_minor(), Some(version("1.3.0")));
+    assert_eq!(v.checked_bump_patch(), Some(version("1.2.4")));
+
+    let max = u64::MAX;
+    let v = &Version::new(max - 1, max - 1, max - 1);
+    assert_eq!(v.bump_major(), Version::new(max, 0, 0));
+    assert_eq!(v.bump_minor(), Version::new(max - 1, max, 0));
+    assert_eq!(v.bump_patch(), Version::new(max - 1, max - 1, max));
+    assert_eq!(v.checked_bump_major(), Some(Version::new(max, 0, 0)));
+    assert_eq!(v.checked_bump_minor(), Some(Version::new(max - 1, max, 0)));
+    assert_eq!(
+        v.checked_bump_patch(),
+        Some(Version::new(max - 1, max - 1, max))
+    );
+
+    // Saturating at u64::MAX, without resetting the lower components.
+    let v = &Version::new(max, max, max);
+    assert_eq!(v.bump_major(), Version::new(max, max, max));
+    assert_eq!(v.bump_minor(), Version::new(max, max, max));
+    assert_eq!(v.bump_patch(), Version::new(max, max, max));
+    assert_eq!(v.checked_bump_major(), None);
+    assert_eq!(v.checked_bump_minor(), None);
+    assert_eq!(v.checked_bump_patch(), None);
+
+    let v = &Version::new(max, 3, 4);
+    assert_eq!(v.bump_major(), Version::new(max, 3, 4));
+    assert_eq!(v.bump_minor(), Version::new(max, 4, 0));
+    assert!(v.bump_major() >= *v);
+
+    let v = &version("18446744073709551615.3.4-rc.1");
+    assert_eq!(v.bump_major(), Version::new(max, 3, 4));
+    assert!(v.bump_major() > *v);
+
+    let v = &Version::new(1, max, 7);
+    assert_eq!(v.bump_major(), Version::new(2, 0, 0));
+    assert_eq!(v.bump_minor(), Version::new(1, max, 7));
+    assert_eq!(v.checked_bump_minor(), None);
+    assert_eq!(v.checked_bump_patch(), Some(Version::new(1, max, 8)));
+}
+
+#[test]
+fn test_numeric_roundtrip() {
+    let mut numbers = vec![0, 1, 9, 10, 99, 100, u64::MAX - 1, u64::MAX];
+    let mut random = Xorshift::new();
+    for _ in 0..1000 {
+        let state = random.next_u64();
+        numbers.push(state >> (state % 64));
+    }
+
+    for window in numbers.windows(3) {
+        let expected = Version::new(window[0], window[1], window[2]);
+        let text = expected.to_string();
+        assert_eq!(version(&text), expected);
+        assert_eq!(version(&text).to_string(), text);
+    }
+
+    let err = version_err("18446744073709551616.0.0");
+    assert_to_string(err, "value of major version number exceeds u64::MAX");
+
+    let err = version_err("0.99999999999999999999.0");
+    assert_to_string(err, "value of minor version number exceeds u64::MAX");
+
+    let err = version_err("0.0.00");
+    assert_to_string(err, "invalid leading zero in patch version number");
+
+    let err = version_err("0.1a.0");
+    assert_to_string(err, "unexpected character 'a' after minor version number");
+
+    let err = version_err("0.1.");
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing patch version number",
+    );
+}
+
+#[test]
+fn test_format_with() {
+    assert_eq!(version("1.2.3").format_with('_'), "1_2_3");
+    assert_eq!(version("1.2.3").format_with('-'), "1-2-3");
+    assert_eq!(version("1.2.3").format_with('.'), "1.2.3");
+    assert_eq!(version("10.0.21-rc.1+build.5").format_with('_'), "10_0_21");
+    assert_eq!(version("0.0.0").format_with('/'), "0/0/0");
+}
+
+#[test]
+fn test_satisfies() {
+    let compatible = [req("^1.2"), req(">=1.4"), req("<1.8")];
+    let conflicting = [req("^1.2"), req("^2")];
+
+    assert!(version("1.5.0").satisfies_all(&compatible));
+    assert!(version("1.5.0").satisfies_any(&compatible));
+    assert!(!version("1.3.0").satisfies_all(&compatible));
+    assert!(version("1.3.0").satisfies_any(&compatible));
+    assert!(!version("3.0.0").satisfies_any(&[req("^1.2"), req("<1.8")]));
+
+    for v in &["1.2.0", "1.9.0", "2.0.0", "2.1.0"] {
+        assert!(!version(v).satisfies_all(&conflicting));
+        assert!(version(v).satisfies_any(&conflicting));
+    }
+
+    let none: [VersionReq; 0] = [];
+    assert!(version("1.0.0").satisfies_all(&none));
+    assert!(!version("1.0.0").satisfies_any(&none));
+}
+
+#[test]
+fn test_with_pre_incremented() {
+    let next = |text: &str| version(text).with_pre_incremented();
+
+    assert_eq!(next("1.0.0-rc"), Some(version("1.0.0-rc.1")));
+    assert_eq!(next("1.0.0-rc.1"), Some(version("1.0.0-rc.2")));
+    assert_eq!(next("1.0.0-rc.9"), Some(version("1.0.0-rc.10")));
+    assert_eq!(
+        next("1.0.0-alpha.beta"),
+        Some(version("1.0.0-alpha.beta.1"))
+    );
+    assert_eq!(next("1.0.0-1"), Some(version("1.0.0-2")));
+    assert_eq!(next("1.0.0-0"), Some(version("1.0.0-1")));
+    assert_eq!(next("1.0.0-rc.1+build.5"), Some(version("1.0.0-rc.2")));
+    assert_eq!(
+        next("1.0.0-rc.18446744073709551615"),
+        Some(version("1.0.0-rc.18446744073709551616")),
+    );
+
+    assert_eq!(next("1.0.0"), None);
+    assert_eq!(next("1.0.0+build"), None);
+
+    let rc = version("1.2.3-rc.1");
+    assert!(rc.with_pre_incremented().unwrap() > rc);
+}
+
+#[test]
+fn test_tilde_caret_req() {
+    let v = &version("1.2.3");
+    assert_eq!(v.tilde_req(), VersionReq::parse("~1.2.3").unwrap());
+    assert_eq!(v.caret_req(), VersionReq::parse("^1.2.3").unwrap());
+    assert_eq!(v.tilde_req().to_string(), "~1.2.3");
+    assert_eq!(v.caret_req().to_string(), "^1.2.3");
+
+    for text in &["1.2.3", "1.2.9"] {
+        assert!(v.tilde_req().matches(&version(text)));
+    }
+    for text in &["1.2.2", "1.3.0", "2.0.0"] {
+        assert!(!v.tilde_req().matches(&version(text)));
+    }
+    for text in &["1.2.3", "1.3.0", "1.9.9"] {
+        assert!(v.caret_req().matches(&version(text)));
+    }
+    for text in &["1.2.2", "2.0.0", "1.3.0-rc"] {
+        assert!(!v.caret_req().matches(&version(text)));
+    }
+
+    let v = &version("0.2.3");
+    assert!(v.caret_req().matches(&version("0.2.9")));
+    assert!(!v.caret_req().matches(&version("0.3.0")));
+
+    let v = &version("1.2.3-rc.1+build");
+    assert_eq!(v.caret_req().to_string(), "^1.2.3-rc.1");
+    assert!(v.tilde_req().matches(v));
+    assert!(v.caret_req().matches(v));
+    assert!(v.caret_req().matches(&version("1.2.3-rc.2")));
+}
+
+#[test]
+fn test_same_major_minor_req() {
+    let v = &version("1.2.3");
+    assert_eq!(v.same_major_req().to_string(), ">=1.0.0 <2.0.0");
+    assert_eq!(v.same_minor_req().to_string(), ">=1.2.0 <1.3.0");
+
+    // Starts from zero, unlike caret and tilde.
+    for text in &["1.0.0", "1.2.0", "1.2.2", "1.9.9"] {
+        assert!(v.same_major_req().matches(&version(text)));
+    }
+    assert!(!v.caret_req().matches(&version("1.0.0")));
+    assert!(!v.tilde_req().matches(&version("1.2.0")));
+    for text in &["0.9.9", "2.0.0", "2.0.0-rc.1", "1.5.0-rc.1"] {
+        assert!(!v.same_major_req().matches(&version(text)));
+    }
+    for text in &["1.2.0", "1.2.2", "1.2.9"] {
+        assert!(v.same_minor_req().matches(&version(text)));
+    }
+    for text in &["1.1.9", "1.3.0", "1.3.0-rc.1", "1.2.0-rc.1"] {
+        assert!(!v.same_minor_req().matches(&version(text)));
+    }
+
+    let v = &version("0.2.3-rc.1+build");
+    assert_eq!(v.same_major_req().to_string(), ">=0.0.0 <1.0.0");
+    assert_eq!(v.same_minor_req().to_string(), ">=0.2.0 <0.3.0");
+    assert!(!v.same_minor_req().matches(v));
+
+    let v = &Version::new(u64::MAX, u64::MAX, 0);
+    assert_eq!(v.same_major_req().to_string(), ">=18446744073709551615.0.0",);
+    assert_eq!(
+        v.same_minor_req().to_string(),
+        ">=18446744073709551615.18446744073709551615.0",
+    );
+}
+
+#[test]
+fn test_patch_window_req() {
+    let v = &version("1.2.5");
+    let req = v.patch_window_req(2);
+    assert_eq!(req.to_string(), "=1.2.3 - =1.2.7");
+    for text in &["1.2.3", "1.2.5", "1.2.7", "1.2.7+build"] {
+        assert!(req.matches(&version(text)), "{}", text);
+    }
+    for text in &["1.2.2", "1.2.8", "1.3.5", "2.2.5", "1.2.5-rc.1"] {
+        assert!(!req.matches(&version(text)), "{}", text);
+    }
+
+    // Tolerance 0 matches the release of `self` only.
+    let req = version("1.2.5-rc.1+build").patch_window_req(0);
+    assert_eq!(req.to_string(), "=1.2.5 - =1.2.5");
+    assert!(req.matches(&version("1.2.5")));
+    assert!(!req.matches(&version("1.2.4")));
+    assert!(!req.matches(&version("1.2.6")));
+    assert!(!req.matches(&version("1.2.5-rc.1")));
+
+    // Saturating at either end.
+    let req = version("1.2.1").patch_window_req(2);
+    assert_eq!(req.to_string(), "=1.2.0 - =1.2.3");
+    assert!(req.matches(&version("1.2.0")));
+    assert!(!req.matches(&version("1.1.9")));
+    let req = Version::new(1, 2, u64::MAX - 1).patch_window_req(u64::MAX);
+    assert_eq!(req.to_string(), "=1.2.0 - =1.2.18446744073709551615");
+}
+
+#[test]
+fn test_clamp_to_req() {
+    let clamp = |ver: &str, req: &str| {
+        let clamped = version(ver).clamp_to_req(&VersionReq::parse(req).unwrap());
+        clamped.map(|ver| ver.to_string())
+    };
+    let some = |ver: &str| Some(ver.to_owned());
+
+    // Already matching.
+    assert_eq!(clamp("1.5.0", "^1.2.3"), some("1.5.0"));
+    assert_eq!(clamp("1.5.0+build", "^1.2.3"), some("1.5.0+build"));
+
+    // Below.
+    assert_eq!(clamp("1.0.0", "^1.2.3"), some("1.2.3"));
+    assert_eq!(clamp("1.0.0", ">1.2.3"), some("1.2.4"));
+    assert_eq!(clamp("1.0.0", ">1.2"), some("1.3.0"));
+    assert_eq!(clamp("1.0.0", ">=1.2.3-rc.1"), some("1.2.3-rc.1"));
+    assert_eq!(clamp("1.0.0", ">1.2.3-rc.1"), some("1.2.3"));
+
+    // Above.
+    assert_eq!(clamp("3.0.0", "<=1.4.0"), some("1.4.0"));
+    assert_eq!(clamp("3.0.0", "^1.2.3"), some("1.2.3"));
+    assert_eq!(clamp("3.0.0", "<1.4.0"), some("0.0.0"));
+    assert_eq!(clamp("2.0.0", "1.2.3 - 1.4.0"), some("1.4.0"));
+    assert_eq!(clamp("2.0.0", "1.2.3 - 1.4"), some("1.2.3"));
+
+    // Between alternatives, rounding up.
+    assert_eq!(clamp("2.5.0", "^1 || ^3.1"), some("3.1.0"));
+    assert_eq!(clamp("5.0.0", "^1 || ^3.1"), some("3.1.0"));
+    assert_eq!(clamp("5.0.0", "<=1.0.0 || ^3.1"), some("3.1.0"));
+    assert_eq!(clamp("5.0.0", "^3.1 || <=1.0.0"), some("3.1.0"));
+
+    // Pre-release within the range.
+    assert_eq!(clamp("1.5.0-rc.1", "^1.2.3"), some("1.5.0"));
+    assert_eq!(clamp("2.0.0-rc.1", "^1.2.3"), some("1.2.3"));
+
+    // Unsatisfiable.
+    assert_eq!(clamp("1.0.0", "<0.0.0"), None);
+    assert_eq!(clamp("1.0.0", ">1.2.3 <1.2.4"), None);
+
+    let versions = [
+        "0.0.0",
+        "0.1.0",
+        "1.0.0",
+        "1.2.3-rc",
+        "1.2.3",
+        "1.5.0-rc.1",
+        "1.9.9",
+        "2.0.0",
+        "3.1.5",
+        "9.0.0",
+    ];
+    let reqs = [
+        "*",
+        "^1.2.3",
+        ">1.2.3",
+        "<1.4",
+        "<=1.4",
+        "~1.2 || >=3.1.5",
+        "=1.2.3-rc",
+        "^0.1 || ^2",
+    ];
+    for req in &reqs {
+        let req = &VersionReq::parse(req).unwrap();
+        for ver in &versions {
+            let clamped = version(ver).clamp_to_req(req).unwrap();
+            assert!(req.matches(&clamped), "{} {} {}", req, ver, clamped);
+        }
+    }
+}
+
+#[test]
+fn test_cmp_with_build() {
+    let cmp = |a: &str, b: &str| version(a).cmp_with_build(&version(b));
+
+    assert_eq!(cmp("1.0.0+a", "1.0.0+b"), Ordering::Less);
+    assert_eq!(cmp("1.0.0+b", "1.0.0+a"), Ordering::Greater);
+    assert_eq!(cmp("1.0.0+a", "1.0.0+a"), Ordering::Equal);
+    assert_eq!(cmp("1.0.0", "1.0.0+a"), Ordering::Less);
+    assert_eq!(cmp("1.0.0+10", "1.0.0+9"), Ordering::Less);
+    assert_eq!(cmp("1.0.0+a.b", "1.0.0+a-b"), Ordering::Greater);
+
+    // Precedence comes first.
+    assert_eq!(cmp("1.0.0+z", "1.0.1+a"), Ordering::Less);
+    assert_eq!(cmp("1.0.0-rc+z", "1.0.0+a"), Ordering::Less);
+    assert_eq!(cmp("1.0.0-alpha.2+a", "1.0.0-alpha.10+a"), Ordering::Less);
+
+    let mut versions = vec![
+        "1.0.0+b",
+        "0.9.0+z",
+        "1.0.0",
+        "1.0.0+a.2",
+        "1.0.0+a",
+        "1.0.0-rc+a",
+    ];
+    versions.sort_by(|a, b| cmp(a, b));
+    assert_eq!(
+        versions,
+        [
+            "0.9.0+z",
+            "1.0.0-rc+a",
+            "1.0.0",
+            "1.0.0+a",
+            "1.0.0+a.2",
+            "1.0.0+b"
+        ],
+    );
+}
+
+#[test]
+fn test_total_version() {
+    let data = ["1.0.0+9", "1.0.0+10", "1.0.0", "1.0.0-rc.1+x", "0.9.0+z"];
+
+    // Version's own Ord compares build metadata identifier by identifier,
+    // like pre-release, so numeric 9 is less than 10.
+    let set: BTreeSet<Version> = data.iter().map(|v| version(v)).collect();
+    let sorted: Vec<String> = set.iter().map(Version::to_string).collect();
+    assert_eq!(
+        sorted,
+        ["0.9.0+z", "1.0.0-rc.1+x", "1.0.0", "1.0.0+9", "1.0.0+10"],
+    );
+
+    // TotalVersion keeps every version too, but compares build metadata as
+    // plain strings.
+    let set: BTreeSet<TotalVersion> = data.iter().map(|v| TotalVersion(version(v))).collect();
+    let sorted: Vec<String> = set.iter().map(|v| v.0.to_string()).collect();
+    assert_eq!(
+        sorted,
+        ["0.9.0+z", "1.0.0-rc.1+x", "1.0.0", "1.0.0+10", "1.0.0+9"],
+    );
+
+    assert!(version("1.0.0+9") < version("1.0.0+10"));
+    assert!(TotalVersion(version("1.0.0+9")) > TotalVersion(version("1.0.0+10")));
+    assert_eq!(
+        TotalVersion(version("1.0.0+9")),
+        TotalVersion(version("1.0.0+9")),
+    );
+}
+
+#[test]
+fn test_triple() {
+    assert_eq!(version("1.2.3").as_triple(), (1, 2, 3));
+    assert_eq!(version("1.2.3-rc.1+build").as_triple(), (1, 2, 3));
+    assert_eq!(Version::from((1, 2, 3)), version("1.2.3"));
+
+    let triple = (u64::MAX, 0, 18);
+    let ver = Version::from(triple);
+    assert!(ver.pre.is_empty());
+    assert!(ver.build.is_empty());
+    assert_eq!(ver.as_triple(), triple);
+
+    for text in &["0.0.0", "1.0.0", "0.18.44", "18446744073709551615.1.2"] {
+        let ver = version(text);
+        assert_eq!(Version::from(ver.as_triple()), ver);
+    }
+}
+
+#[test]
+fn test_array() {
+    const SUPPORTED: &[Version] = &[
+        Version::from_array([1, 0, 0]),
+        Version::from_array([1, 4, 2]),
+        Version::new(2, 0, 0),
+    ];
+    const ARRAY: [u64; 3] = SUPPORTED[1].to_array();
+
+    assert_eq!(SUPPORTED[0], version("1.0.0"));
+    assert_eq!(SUPPORTED[1], version("1.4.2"));
+    assert!(SUPPORTED
+        .iter()
+        .all(|v| v.pre.is_empty() && v.build.is_empty()));
+    assert_eq!(ARRAY, [1, 4, 2]);
+
+    assert_eq!(version("1.2.3-rc.1+build").to_array(), [1, 2, 3]);
+    for text in &["0.0.0", "0.18.44", "18446744073709551615.1.2"] {
+        let v = &version(text);
+        assert_eq!(Version::from_array(v.to_array()), *v);
+    }
+}
+
+#[test]
+fn test_partial_version() {
+    let partial = |text: &str| PartialVersion::parse(text).unwrap();
+
+    let p = partial("1");
+    assert_eq!((p.major, p.minor, p.patch), (1, None, None));
+    let p = partial("1.2");
+    assert_eq!((p.major, p.minor, p.patch), (1, Some(2), None));
+    let p = partial("1.2.0");
+    assert_eq!((p.major, p.minor, p.patch), (1, Some(2), Some(0)));
+    assert_ne!(partial("1.2"), partial("1.2.0"));
+
+    let p = partial("1.2.3-rc.1+build.5");
+    assert_eq!(p.pre, prerelease("rc.1"));
+    assert_eq!(p.build, build_metadata("build.5"));
+
+    for text in &[
+        "0",
+        "1.2",
+        "1.2.0",
+        "1.2.3-rc.1",
+        "1.2.3+build",
+        "1.2.3-rc+build",
+    ] {
+        assert_to_string(partial(text), text);
+    }
+
+    let err = |text: &str| PartialVersion::parse(text).unwrap_err();
+    assert_to_string(err(""), "empty string, expected a semver version");
+    assert_to_string(
+        err("1.2-rc"),
+        "unexpected character '-' after minor version number",
+    );
+    assert_to_string(
+        err("1+build"),
+        "unexpected character '+' after major version number",
+    );
+    assert_to_string(
+        err("1.x"),
+        "unexpected character 'x' while parsing minor version number",
+    );
+    assert_to_string(
+        err("1.2.3.4"),
+        "unexpected character '.' after patch version number",
+    );
+    assert_to_string(err("01.2"), "invalid leading zero in major version number");
+}
+
+#[test]
+fn test_build_metadata_charset() {
+    for text in &[
+        "1.0.0+SHA.ABCDEF",
+        "1.0.0+20130313144700",
+        "1.0.0+exp.sha.5114f85",
+        "1.0.0+0",
+        "1.0.0+007",
+        "1.0.0+--",
+        "1.0.0-rc.1+Build-42.aBc",
+    ] {
+        let ver = version(text);
+        assert_to_string(&ver, text);
+        let build = &text[text.find('+').unwrap() + 1..];
+        assert_eq!(ver.build.as_str(), build);
+    }
+
+    for text in &["1.0.0+.", "1.0.0+", "1.0.0+a.", "1.0.0+.a", "1.0.0+a..b"] {
+        let err = version_err(text);
+        assert_to_string(err, "empty identifier segment in build metadata");
+    }
+
+    for text in &["1.0.0+a_b", "1.0.0+ab!"] {
+        let err = version_err(text);
+        assert!(
+            err.to_string().starts_with("unexpected character"),
+            "{}",
+            err
+        );
+    }
+}
+
+#[test]
+fn test_sortable_key() {
+    let key = |text: &str| version(text).to_sortable_key();
+
+    let mut expected = vec![0, 0, 0, 0, 0, 0, 0, 1];
+    expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 2]);
+    expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 3]);
+    expected.push(1);
+    assert_eq!(key("1.2.3"), expected);
+    assert_eq!(key("1.2.3+build"), expected);
+
+    // Spec examples, in increasing order.
+    let ordered = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "1.0.1-0",
+        "1.0.1",
+        "1.1.0",
+        "2.0.0",
+        "256.0.0",
+    ];
+    for pair in ordered.windows(2) {
+        assert!(key(pair[0]) < key(pair[1]), "{} {}", pair[0], pair[1]);
+    }
+
+    let pres = [
+        "",
+        "0",
+        "1",
+        "9",
+        "10",
+        "99999999999999999999",
+        "a",
+        "a.0",
+        "a.1",
+        "a.b",
+        "a-b",
+        "ab",
+        "A",
+        "Z.1",
+        "-",
+        "0a",
+        "rc.1.2",
+        "rc.1.a",
+        "1.rc",
+        "alpha",
+        "alpha1",
+        "beta.11.x",
+    ];
+    let mut versions = Vec::new();
+    let mut random = Xorshift::new();
+    for _ in 0..1000 {
+        let state = random.next_u64();
+        let mut ver = Version::new(state % 3, (state >> 8) % 3, (state >> 16) % 300);
+        let pre = pres[(state >> 24) as usize % pres.len()];
+        ver.pre = Prerelease::new(pre).unwrap();
+        versions.push(ver);
+    }
+    versions.push(Version::new(u64::MAX, u64::MAX, u64::MAX));
+
+    for a in &versions {
+        let a_key = a.to_sortable_key();
+        assert_eq!(Version::from_sortable_key(&a_key).as_ref(), Some(a));
+        for b in versions.iter().take(50) {
+            assert_eq!(a_key.cmp(&b.to_sortable_key()), a.cmp(b), "{} {}", a, b);
+        }
+    }
+
+    let ver = &version("1.2.3-rc.1+build");
+    assert_eq!(
+        Version::from_sortable_key(&ver.to_sortable_key()),
+        Some(version("1.2.3-rc.1")),
+    );
+
+    let valid = &key("1.2.3-rc.1");
+    for bad in &[
+        &valid[..0],
+        &valid[..24],
+        &valid[..valid.len() - 1],
+        &[&valid[..], &[0]].concat()[..],
+        &[&key("1.2.3")[..], &[0]].concat()[..],
+        &[&key("1.2.3")[..24], &[0]].concat()[..],
+        &[&key("1.2.3")[..24], &[2, b'1', 0]].concat()[..],
+        &[&key("1.2.3")[..24], &[2, b'a', b'!', 0]].concat()[..],
+        &[&key("1.2.3")[..24], &[7]].concat()[..],
+    ] {
+        assert_eq!(Version::from_sortable_key(bad), None, "{:?}", bad);
+    }
+}
+
+#[test]
+fn test_is_recoverable() {
+    // Leading zero in a number.
+    for text in &["01.2.3", "1.02.3", "1.2.03"] {
+        assert!(version_err(text).is_recoverable(), "{}", text);
+    }
+    // Missing component.
+    for text in &["1", "1.", "1.2", "1.2."] {
+        assert!(version_err(text).is_recoverable(), "{}", text);
+    }
+    // Surrounding whitespace.
+    for text in &[" 1.2.3", "1.2.3\n", "1.2.3-rc.1 ", "1.2.3+build\t"] {
+        assert!(version_err(text).is_recoverable(), "{:?}", text);
+    }
+
+    for text in &[
+        "",
+        "1.2.3-rc.01",
+        "1.2 .3",
+        "1 .2.3",
+        "v1.2.3",
+        "a.b.c",
+        "1.2.3.4",
+        "1.2.3-",
+        "1.2.3-rc..1",
+        "1.2.3+",
+        "1.2.3_x",
+        "18446744073709551616.0.0",
+    ] {
+        assert!(!version_err(text).is_recoverable(), "{:?}", text);
+    }
+
+    assert!(!Prerelease::new("rc!").unwrap_err().is_recoverable());
+    for text in &["^1.2.3, ~2", "*, 1", "1.*.3", "1.2-rc"] {
+        let err = semver::VersionReq::parse(text).unwrap_err();
+        assert!(!err.is_recoverable(), "{}", text);
+    }
+    let err = semver::VersionReq::parse_no_prerelease("^1.2.3-rc").unwrap_err();
+    assert!(!err.is_recoverable());
+    let err = semver::VersionReq::parse("^01.2").unwrap_err();
+    assert!(err.is_recoverable());
+
+    // A missing major number is indistinguishable from the end of input after
+    // one, as in `1`.
+    for text in &["^", ">=", "1.2.3 - "] {
+        let err = semver::VersionReq::parse(text).unwrap_err();
+        assert_to_string(
+            &err,
+            "unexpected end of input while parsing major version number",
+        );
+        assert!(err.is_recoverable(), "{}", text);
+    }
+}
+
+#[test]
+fn test_distance() {
+    let distance = |a: &str, b: &str| version(a).distance(&version(b));
+
+    assert_eq!(distance("1.2.3", "1.2.3"), 0);
+    assert_eq!(distance("1.2.3", "1.2.3-rc.1+build"), 0);
+    assert_eq!(distance("1.2.3", "1.2.0"), 3);
+    assert_eq!(distance("1.2.0", "1.2.3"), 3);
+    assert_eq!(distance("1.2.3", "1.3.3"), 1_000_000);
+    assert_eq!(distance("1.2.3", "2.2.3"), 1_000_000_000_000);
+    assert_eq!(distance("1.2.3", "0.0.0"), 1_000_002_000_003);
+
+    // Closer in a higher component always wins.
+    let target = "1.2.3";
+    let mut candidates = [
+        "3.0.0", "1.2.9", "1.3.0", "0.9.9", "2.2.3", "1.2.2", "1.0.0",
+    ];
+    candidates.sort_by_key(|v| distance(target, v));
+    assert_eq!(
+        candidates,
+        ["1.2.2", "1.2.9", "1.3.0", "1.0.0", "2.2.3", "0.9.9", "3.0.0"],
+    );
+    assert!(distance(target, "1.2.999999999") < distance(target, "1.3.3"));
+    assert!(distance(target, "1.999999999.0") < distance(target, "2.2.3"));
+
+    // Saturation.
+    let max = u64::MAX;
+    assert_eq!(
+        Version::new(0, 0, 0).distance(&Version::new(0, 0, max)),
+        999_999,
+    );
+    assert_eq!(
+        Version::new(0, 0, 0).distance(&Version::new(max, max, max)),
+        u128::from(max) * 1_000_000_000_000 + 999_999_999_999,
+    );
+}
+
+#[test]
+fn test_parse_bytes() {
+    for text in &["1.2.3", "0.0.0", "1.2.3-rc.1+build.5", "1.2.3+x-y"] {
+        let v = Version::parse_bytes(text.as_bytes()).unwrap();
+        assert_eq!(v, version(text));
+    }
+
+    // The same errors as for a string.
+    for text in &["", "1.2", "1.2.3-", "01.2.3", "1.2.3 ", "1.2.3-rc..1"] {
+        let err = Version::parse_bytes(text.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), version_err(text).to_string());
+    }
+
+    // Non-ASCII.
+    let err = Version::parse_bytes(b"\xff1.2.3").unwrap_err();
+    assert_to_string(err, "unexpected character in major version number");
+
+    let err = Version::parse_bytes(b"1.2\xff").unwrap_err();
+    assert_to_string(err, "unexpected character in minor version number");
+
+    let err = Version::parse_bytes(b"1.2.3\xff").unwrap_err();
+    assert_to_string(err, "unexpected character in patch version number");
+
+    let err = Version::parse_bytes(b"1.2.3-rc\xff").unwrap_err();
+    assert_to_string(err, "unexpected character in pre-release identifier");
+
+    for bytes in &[&b"1.2.3-\xff"[..], b"1.2.3-rc.\xff", b"1.2.3-\xffrc"] {
+        let err = Version::parse_bytes(bytes).unwrap_err();
+        assert_to_string(err, "unexpected character in pre-release identifier");
+    }
+    for bytes in &[&b"1.2.3+\xff"[..], b"1.2.3-rc+b.\xff"] {
+        let err = Version::parse_bytes(bytes).unwrap_err();
+        assert_to_string(err, "unexpected character in build metadata");
+    }
+
+    // An empty identifier before the cut-off point is still reported.
+    let err = Version::parse_bytes(b"1.2.3-rc..\xff").unwrap_err();
+    assert_to_string(err, "empty identifier segment in pre-release identifier");
+
+    let err = Version::parse_bytes("1.2.3+bü".as_bytes()).unwrap_err();
+    assert_to_string(err, "unexpected character in build metadata");
+
+    // An error in the ASCII text before the non-ASCII byte.
+    let err = Version::parse_bytes(b"1.2.3 \xff").unwrap_err();
+    assert_to_string(err, "unexpected character ' ' after patch version number");
+
+    let err = Version::parse_bytes(b"01.2.3\xff").unwrap_err();
+    assert_to_string(err, "invalid leading zero in major version number");
+}
+
+#[test]
+fn test_parse_trimmed() {
+    for text in &[" 1.2.3\n", "1.2.3\r\n", "\t1.2.3  ", "1.2.3"] {
+        assert_eq!(Version::parse_trimmed(text).unwrap(), version("1.2.3"));
+    }
+    assert_eq!(
+        Version::parse_trimmed(" 1.2.3-rc.1+build\n").unwrap(),
+        version("1.2.3-rc.1+build"),
+    );
+
+    let err = version_err("1.2.3 ");
+    assert_to_string(err, "unexpected character ' ' after patch version number");
+
+    let err = Version::parse_trimmed("1.2 .3").unwrap_err();
+    assert_to_string(err, "unexpected character ' ' after minor version number");
+
+    let err = Version::parse_trimmed(" \n ").unwrap_err();
+    assert_to_string(err, "empty string, expected a semver version");
+}
+
+#[test]
+fn test_parse_prefix() {
+    let prefix = |text| Version::parse_prefix(text).unwrap();
+
+    assert_eq!(prefix("1.2.3"), (version("1.2.3"), ""));
+    assert_eq!(prefix("1.2.3 rest"), (version("1.2.3"), " rest"));
+    assert_eq!(prefix("1.2.3-rc.1 rest"), (version("1.2.3-rc.1"), " rest"));
+    assert_eq!(prefix("1.2.3+build.5/x"), (version("1.2.3+build.5"), "/x"));
+    assert_eq!(prefix("1.2.3-rc.1+b,2"), (version("1.2.3-rc.1+b"), ",2"));
+    assert_eq!(prefix("1.2.3.4"), (version("1.2.3"), ".4"));
+    assert_eq!(prefix("1.2.3."), (version("1.2.3"), "."));
+    assert_eq!(prefix("1.2.3_x"), (version("1.2.3"), "_x"));
+
+    // Greedy.
+    assert_eq!(prefix("1.2.3-linux-x64"), (version("1.2.3-linux-x64"), ""));
+    assert_eq!(prefix("1.2.3-rc.1.tar"), (version("1.2.3-rc.1.tar"), ""));
+    assert_eq!(prefix("1.2.3+x86_64"), (version("1.2.3+x86"), "_64"));
+    assert_eq!(prefix("1.2.3x"), (version("1.2.3"), "x"));
+
+    let err = Version::parse_prefix("1.2 rest").unwrap_err();
+    assert_to_string(err, "unexpected character ' ' after minor version number");
+
+    let err = Version::parse_prefix("1.2.3- rest").unwrap_err();
+    assert_to_string(err, "empty identifier segment in pre-release identifier");
+
+    let err = Version::parse_prefix("1.2.3-rc..1").unwrap_err();
+    assert_to_string(err, "empty identifier segment in pre-release identifier");
+
+    let err = Version::parse_prefix("1.2.3-rc.01").unwrap_err();
+    assert_to_string(err, "invalid leading zero in pre-release identifier");
+
+    let err = Version::parse_prefix("").unwrap_err();
+    assert_to_string(err, "empty string, expected a semver version");
+}
+
+#[test]
+fn test_parse_git_describe() {
+    let describe = |text| Version::parse_git_describe(text).unwrap();
+    let ahead = |commits_ahead, sha: &str| {
+        Some(GitDescribe {
+            commits_ahead,
+            sha: sha.to_owned(),
+        })
+    };
+
+    // Tagged exactly.
+    assert_eq!(describe("1.2.3"), (version("1.2.3"), None));
+    assert_eq!(describe("1.2.3-rc.1"), (version("1.2.3-rc.1"), None));
+    assert_eq!(describe("1.2.3+build"), (version("1.2.3+build"), None));
+
+    // Commits ahead.
+    assert_eq!(
+        describe("1.2.3-4-gabc1234"),
+        (version("1.2.3"), ahead(4, "abc1234"))
+    );
+    assert_eq!(describe("1.2.3-0-g0"), (version("1.2.3"), ahead(0, "0")));
+    assert_eq!(
+        describe("1.2.3-rc.1-12-gDEADBEEF"),
+        (version("1.2.3-rc.1"), ahead(12, "DEADBEEF")),
+    );
+    assert_eq!(
+        describe("1.2.3-4-5-gabc1234"),
+        (version("1.2.3-4"), ahead(5, "abc1234")),
+    );
+
+    // Not a describe suffix, so part of the pre-release.
+    assert_eq!(describe("1.2.3-4-gxyz"), (version("1.2.3-4-gxyz"), None));
+    assert_eq!(describe("1.2.3-x-gabc"), (version("1.2.3-x-gabc"), None));
+    assert_eq!(describe("1.2.3-4-abc"), (version("1.2.3-4-abc"), None));
+    assert_eq!(describe("1.2.3-4-g"), (version("1.2.3-4-g"), None));
+    assert_eq!(
+        describe("1.2.3-4-gabc1234-dirty"),
+        (version("1.2.3-4-gabc1234-dirty"), None),
+    );
+
+    let err = Version::parse_git_describe("v1.2.3-4-gabc1234").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected character 'v' while parsing major version number",
+    );
+
+    let err = Version::parse_git_describe("1.2-4-gabc1234").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing minor version number",
+    );
+}
+
+#[test]
+fn test_size() {
+    let ptr = mem::size_of::<usize>();
+    assert_eq!(mem::size_of::<Prerelease>(), ptr);
+    assert_eq!(mem::size_of::<BuildMetadata>(), ptr);
+    assert_eq!(mem::size_of::<Version>(), 3 * 8 + 2 * ptr);
+    assert_eq!(mem::size_of::<Option<Version>>(), mem::size_of::<Version>());
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(mem::size_of::<Version>(), 40);
+    }
+
+    // Parsing, display, and ordering of identifiers on either side of the
+    // inline length limit.
+    let mut versions = Vec::new();
+    for text in &[
+        "1.0.0",
+        "1.0.0-a",
+        "1.0.0-abcdefgh",
+        "1.0.0-abcdefghi",
+        "1.0.0+12345678",
+        "1.0.0+123456789",
+        "1.0.0-abcdefgh+123456789",
+    ] {
+        let ver = version(text);
+        assert_to_string(&ver, text);
+        assert_eq!(ver, ver.clone());
+        versions.push(ver);
+    }
+    versions.sort();
+    let sorted: Vec<String> = versions.iter().map(Version::to_string).collect();
+    assert_eq!(
+        sorted,
+        [
+            "1.0.0-a",
+            "1.0.0-abcdefgh",
+            "1.0.0-abcdefgh+123456789",
+            "1.0.0-abcdefghi",
+            "1.0.0",
+            "1.0.0+12345678",
+            "1.0.0+123456789",
+        ],
+    );
+}
+
+#[test]
+fn test_loosest_req_including() {
+    let loosest = |a: &str, b: &str| {
+        let (a, b) = (version(a), version(b));
+        let req = Version::loosest_req_including(&a, &b);
+        assert!(req.matches(&a) && req.matches(&b), "{} {} {}", req, a, b);
+        assert_eq!(req, Version::loosest_req_including(&b, &a));
+        req.to_string()
+    };
+
+    // Same major and minor.
+    assert_eq!(loosest("1.2.3", "1.2.7"), "~1.2.3");
+    assert_eq!(loosest("1.2.7", "1.2.3"), "~1.2.3");
+    assert_eq!(loosest("1.2.3", "1.2.3"), "~1.2.3");
+    assert_eq!(loosest("0.2.3", "0.2.9"), "~0.2.3");
+    assert_eq!(loosest("0.0.1", "0.0.3"), "~0.0.1");
+    assert_eq!(loosest("1.2.3-rc.1", "1.2.4"), "~1.2.3-rc.1");
+    assert_eq!(loosest("1.2.3+a", "1.2.4+b"), "~1.2.3");
+
+    // Same major.
+    assert_eq!(loosest("1.2.3", "1.5.0"), "^1.2.3");
+    assert_eq!(loosest("3.9.0", "3.0.1"), "^3.0.1");
+
+    // Neither.
+    assert_eq!(loosest("1.2.3", "2.0.0"), "=1.2.3 - =2.0.0");
+    assert_eq!(loosest("0.1.0", "0.2.0"), "=0.1.0 - =0.2.0");
+    assert_eq!(loosest("0.9.0", "1.0.0"), "=0.9.0 - =1.0.0");
+
+    // A higher pre-release is not matched by tilde or caret.
+    assert_eq!(loosest("1.2.3", "1.2.4-rc.1"), "=1.2.3 - =1.2.4-rc.1");
+    assert_eq!(loosest("1.2.3", "1.5.0-rc.1"), "=1.2.3 - =1.5.0-rc.1");
+    assert_eq!(loosest("1.2.3-rc.1", "1.2.3-rc.2"), "~1.2.3-rc.1");
+}
+
+#[test]
+fn test_matches_tag() {
+    let v = &version("1.2.3");
+    assert!(v.matches_tag("1"));
+    assert!(v.matches_tag("1.2"));
+    assert!(v.matches_tag("1.2.3"));
+    assert!(v.matches_tag("1.2.3+build"));
+    assert!(!v.matches_tag("1.2.4"));
+    assert!(!v.matches_tag("1.3"));
+    assert!(!v.matches_tag("2"));
+    assert!(!v.matches_tag("1.2.3-rc.1"));
+    assert!(!v.matches_tag(""));
+    assert!(!v.matches_tag("1.x"));
+    assert!(!v.matches_tag("latest"));
+
+    let v = &version("1.2.3-rc.1+build");
+    assert!(v.matches_tag("1.2.3-rc.1"));
+    assert!(!v.matches_tag("1.2.3"));
+    assert!(!v.matches_tag("1.2"));
+    assert!(!v.matches_tag("1"));
+    assert!(!v.matches_tag("1.2.3-rc.2"));
+}
+
+#[test]
+fn test_parse_preserve_zeros() {
+    let padded = Version::parse_preserve_zeros("01.02.03").unwrap();
+    assert_eq!(*padded.version(), Version::new(1, 2, 3));
+    assert_eq!(padded.as_str(), "01.02.03");
+    assert_to_string(&padded, "01.02.03");
+    assert_eq!(padded.clone().into_version(), Version::new(1, 2, 3));
+
+    let padded = Version::parse_preserve_zeros("000.0.010-rc.1+0012").unwrap();
+    assert_eq!(*padded.version(), version("0.0.10-rc.1+0012"));
+    assert_to_string(&padded, "000.0.010-rc.1+0012");
+
+    // Comparisons are numeric.
+    let a = Version::parse_preserve_zeros("1.02.3").unwrap();
+    let b = Version::parse_preserve_zeros("1.2.3").unwrap();
+    let c = Version::parse_preserve_zeros("1.10.0").unwrap();
+    assert_eq!(a, b);
+    assert!(a < c);
+    assert!(VersionReq::parse("^1.2").unwrap().matches(a.version()));
+
+    let err = Version::parse_preserve_zeros("1.0.0-rc.01").unwrap_err();
+    assert_to_string(err, "invalid leading zero in pre-release identifier");
+    let err = Version::parse_preserve_zeros("01.0").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing minor version number",
+    );
+    let err = Version::parse_preserve_zeros("1.0a.0").unwrap_err();
+    assert_to_string(err, "unexpected character 'a' after minor version number");
+
+    // Strict parsing still rejects them.
+    let err = version_err("01.02.03");
+    assert_to_string(err, "invalid leading zero in major version number");
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(
+        format!("{:?}", version("1.2.3")),
+        "Version { major: 1, minor: 2, patch: 3 }",
+    );
+    assert_eq!(
+        format!("{:?}", version("1.2.3-rc.1")),
+        "Version { major: 1, minor: 2, patch: 3, pre: Prerelease(\"rc.1\") }",
+    );
+    assert_eq!(
+        format!("{:?}", version("1.2.3-rc.1+build.5")),
+        "Version { major: 1, minor: 2, patch: 3, pre: Prerelease(\"rc.1\"), build: BuildMetadata(\"build.5\") }",
+    );
+    assert_eq!(
+        format!("{:#?}", version("0.0.1+build")),
+        "Version {\n    major: 0,\n    minor: 0,\n    patch: 1,\n    build: BuildMetadata(\"build\"),\n}",
+    );
+}
+
+#[test]
+fn test_predecessor() {
+    let predecessor = |text: &str| version(text).predecessor().map(|v| v.to_string());
+    let max = u64::MAX;
+
+    assert_eq!(predecessor("1.2.3-0"), Some("1.2.2".to_owned()));
+    assert_eq!(predecessor("1.2.3-0+build"), Some("1.2.2".to_owned()));
+    assert_eq!(predecessor("1.2.0-0"), Some(format!("1.1.{}", max)));
+    assert_eq!(predecessor("1.0.0-0"), Some(format!("0.{0}.{0}", max)));
+    assert_eq!(predecessor("0.0.1-0"), Some("0.0.0".to_owned()));
+    assert_eq!(predecessor("0.0.0-0"), None);
+
+    assert_eq!(predecessor("1.2.3-rc.1.0"), Some("1.2.3-rc.1".to_owned()));
+    assert_eq!(predecessor("1.2.3-0.0"), Some("1.2.3-0".to_owned()));
+    assert_eq!(predecessor("1.2.3-rc.0.0"), Some("1.2.3-rc.0".to_owned()));
+
+    // No greatest version below these.
+    assert_eq!(predecessor("1.2.3"), None);
+    assert_eq!(predecessor("0.0.0"), None);
+    assert_eq!(predecessor("1.2.3-rc.1"), None);
+    assert_eq!(predecessor("1.2.3-1"), None);
+    assert_eq!(predecessor("1.2.3-rc.10"), None);
+    assert_eq!(predecessor("1.2.3-00a"), None);
+
+    // `<v` is `<=predecessor`.
+    for text in &["1.2.3-0", "1.0.0-0", "1.2.3-rc.1.0"] {
+        let v = &version(text);
+        let prev = v.predecessor().unwrap();
+        assert!(prev < *v);
+        let less = VersionReq::parse(&format!("<{}", v)).unwrap();
+        let less_eq = VersionReq::parse(&format!("<={}", prev)).unwrap();
+        for probe in &[
+            prev.clone(),
+            v.clone(),
+            version("0.0.1"),
+            version("1.2.3-rc.1"),
+        ] {
+            assert_eq!(
+                less.matches(probe),
+                less_eq.matches(probe),
+                "{} {}",
+                text,
+                probe
+            );
+        }
+    }
+}
+
+#[test]
+fn test_group_by_minor() {
+    let versions: Vec<Version> = [
+        "1.2.0",
+        "0.9.3",
+        "1.10.0",
+        "1.2.0-rc.1",
+        "1.0.0",
+        "1.2.1",
+        "0.9.0",
+        "1.2.0+build",
+        "2.0.0",
+    ]
+    .iter()
+    .map(|v| version(v))
+    .collect();
+    let groups = semver::group_by_minor(&versions);
+
+    let buckets: Vec<((u64, u64), Vec<String>)> = groups
+        .iter()
+        .map(|(key, group)| (*key, group.iter().map(|v| v.to_string()).collect()))
+        .collect();
+    assert_eq!(
+        buckets,
+        [
+            ((0, 9), vec!["0.9.0".to_owned(), "0.9.3".to_owned()]),
+            ((1, 0), vec!["1.0.0".to_owned()]),
+            (
+                (1, 2),
+                vec![
+                    "1.2.0-rc.1".to_owned(),
+                    "1.2.0".to_owned(),
+                    "1.2.0+build".to_owned(),
+                    "1.2.1".to_owned(),
+                ],
+            ),
+            ((1, 10), vec!["1.10.0".to_owned()]),
+            ((2, 0), vec!["2.0.0".to_owned()]),
+        ],
+    );
+
+    assert!(semver::group_by_minor(&[]).is_empty());
+}
+
+#[test]
+fn test_build_sort() {
+    // Precedence-equal, but ordered by build metadata so that Ord agrees with
+    // Eq, with no build metadata first.
+    assert!(!(version("1.0.0+build") < version("1.0.0")));
+    assert!(version("1.0.0") < version("1.0.0+build"));
+    assert_ne!(version("1.0.0"), version("1.0.0+build"));
+    assert!(version("1.0.0+a") < version("1.0.0+b"));
+    assert!(version("1.0.0+9") < version("1.0.0+10"));
+    assert!(version("1.0.0-rc.1+z") < version("1.0.0+a"));
+    assert!(version("1.0.0+z") < version("1.0.1"));
+
+    let mut versions = [
+        version("1.0.0+b"),
+        version("1.0.1"),
+        version("1.0.0+a"),
+        version("0.9.0+z"),
+        version("1.0.0"),
+        version("1.0.0-rc.1+b"),
+    ];
+    versions.sort();
+    let sorted: Vec<String> = versions.iter().map(Version::to_string).collect();
+    assert_eq!(
+        sorted,
+        [
+            "0.9.0+z",
+            "1.0.0-rc.1+b",
+            "1.0.0",
+            "1.0.0+a",
+            "1.0.0+b",
+            "1.0.1"
+        ],
+    );
+
+    // Build metadata plays no part in matching.
+    let r = &VersionReq::parse("=1.0.0").unwrap();
+    assert!(versions[2..5].iter().all(|v| r.matches(v)));
+}
+
+#[test]
+fn test_parse_collect_errors() {
+    let errors = Version::parse_collect_errors("1.2.3-01+.").unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_to_string(
+        &errors[0].1,
+        "invalid leading zero in pre-release identifier",
+    );
+    assert_eq!(errors[0].0, 6);
+    assert_to_string(&errors[1].1, "empty identifier segment in build metadata");
+    assert_eq!(errors[1].0, 9);
+
+    let errors = Version::parse_collect_errors("1.x.3-rc..1+b_1").unwrap_err();
+    assert_eq!(errors.len(), 3);
+    assert_to_string(
+        &errors[0].1,
+        "unexpected character 'x' while parsing minor version number",
+    );
+    assert_eq!(errors[0].0, 0);
+    assert_eq!(errors[1].0, 6);
+    assert_eq!(errors[2].0, 12);
+
+    // Only the malformed parts are reported.
+    let errors = Version::parse_collect_errors("1.2-rc.1+build").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_to_string(
+        &errors[0].1,
+        "unexpected end of input while parsing minor version number",
+    );
+    assert_eq!(errors[0].0, 0);
+
+    let errors = Version::parse_collect_errors("1.2.3-").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_to_string(
+        &errors[0].1,
+        "empty identifier segment in pre-release identifier",
+    );
+    assert_eq!(errors[0].0, 6);
+
+    let errors = Version::parse_collect_errors("").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_to_string(&errors[0].1, "empty string, expected a semver version");
+
+    let v = Version::parse_collect_errors("1.2.3-rc.1+build.5").unwrap();
+    assert_eq!(v, version("1.2.3-rc.1+build.5"));
+}
+
+#[test]
+fn test_patch_range() {
+    let range = |from: &str, to: &str| {
+        Version::patch_range(&version(from), &version(to))
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        range("1.2.0", "1.2.5"),
+        ["1.2.0", "1.2.1", "1.2.2", "1.2.3", "1.2.4"],
+    );
+    assert_eq!(range("1.2.3", "1.2.4"), ["1.2.3"]);
+
+    // Pre-releases and build metadata at either end.
+    assert_eq!(
+        range("1.2.3-rc.1", "1.2.5"),
+        ["1.2.3-rc.1", "1.2.3", "1.2.4"]
+    );
+    assert_eq!(range("1.2.3", "1.2.5-rc.1"), ["1.2.3", "1.2.4"]);
+    assert_eq!(range("1.2.3-rc.1", "1.2.3"), ["1.2.3-rc.1"]);
+    assert_eq!(range("1.2.3-rc.1", "1.2.3-rc.2"), ["1.2.3-rc.1"]);
+    assert_eq!(range("1.2.3+build", "1.2.5"), ["1.2.3+build", "1.2.4"]);
+
+    // Empty.
+    assert!(range("1.2.5", "1.2.5").is_empty());
+    assert!(range("1.2.5", "1.2.0").is_empty());
+    assert!(range("1.2.5", "1.2.5+build").is_empty());
+    assert!(range("1.2.0", "1.3.0").is_empty());
+    assert!(range("1.2.0", "2.2.5").is_empty());
+
+    let max = u64::MAX;
+    let from = &Version::new(1, 2, max - 1);
+    let to = &Version::new(1, 2, max);
+    assert_eq!(
+        Version::patch_range(from, to).collect::<Vec<_>>(),
+        [Version::new(1, 2, max - 1)]
+    );
+    assert_eq!(Version::patch_range(to, to).count(), 0);
+}
+
+#[test]
+fn test_pre_leading_zero() {
+    // A numeric identifier, which is all digits, must not have a leading zero.
+    for text in &[
+        "1.0.0-01",
+        "1.0.0-rc.01",
+        "1.0.0-00",
+        "1.0.0-rc.1.007",
+        "1.0.0-01+build",
+    ] {
+        let err = version_err(text);
+        assert_to_string(err, "invalid leading zero in pre-release identifier");
+    }
+    let err = Prerelease::new("rc.01").unwrap_err();
+    assert_to_string(err, "invalid leading zero in pre-release identifier");
+    let err = VersionReq::parse("=1.0.0-rc.01").unwrap_err();
+    assert_to_string(err, "invalid leading zero in pre-release identifier");
+
+    // An alphanumeric identifier, with any non-digit, may start with zero.
+    for text in &[
+        "1.0.0-0rc",
+        "1.0.0-rc.0a",
+        "1.0.0-00-1",
+        "1.0.0-0",
+        "1.0.0-rc.0",
+    ] {
+        let v = version(text);
+        assert_to_string(&v, text);
+    }
+    // Compared as text, so after any numeric identifier.
+    assert!(version("1.0.0-0rc") > version("1.0.0-999"));
+
+    // Build metadata may have leading zeros anywhere.
+    let v = version("1.0.0-rc.1+01.007");
+    assert_eq!(v.build.as_str(), "01.007");
+}
+
+#[test]
+fn test_same_release() {
+    let same = |a: &str, b: &str| version(a).same_release(&version(b));
+
+    assert!(same("1.2.3-rc.1", "1.2.3"));
+    assert!(same("1.2.3", "1.2.3-rc.1"));
+    assert!(same("1.2.3-alpha", "1.2.3-beta.2"));
+    assert!(same("1.2.3+build.1", "1.2.3+build.2"));
+    assert!(same("1.2.3", "1.2.3"));
+
+    assert!(!same("1.2.3", "1.2.4"));
+    assert!(!same("1.2.3-rc.1", "1.2.4-rc.1"));
+    assert!(!same("1.2.3", "1.3.3"));
+    assert!(!same("1.2.3", "2.2.3"));
+}
+
+#[test]
+fn test_empty_number() {
+    // An empty number is reported at the position where one was expected, by
+    // the strict and the more lenient parsers alike. Only a missing number at
+    // the end is recoverable, by filling in zero.
+    let cases = [
+        (
+            ".2.3",
+            "unexpected character '.' while parsing major version number",
+        ),
+        (
+            "1..3",
+            "unexpected character '.' while parsing minor version number",
+        ),
+        (
+            "1.2..3",
+            "unexpected character '.' while parsing patch version number",
+        ),
+        (
+            "1.",
+            "unexpected end of input while parsing minor version number",
+        ),
+        (
+            "1.2.",
+            "unexpected end of input while parsing patch version number",
+        ),
+    ];
+    for &(text, message) in &cases {
+        let err = version_err(text);
+        assert_to_string(&err, message);
+        assert_eq!(err.is_recoverable(), text.ends_with('.'), "{}", text);
+
+        let err = Version::parse_trimmed(text).unwrap_err();
+        assert_to_string(err, message);
+        let err = Version::parse_preserve_zeros(text).unwrap_err();
+        assert_to_string(err, message);
+        let err = PartialVersion::parse(text).unwrap_err();
+        assert_to_string(err, message);
+    }
+}
+
+#[test]
+fn test_select_update() {
+    let available = [
+        "1.1.0",
+        "1.2.3",
+        "1.2.3+build",
+        "1.2.4",
+        "1.3.0-rc.1",
+        "1.3.0",
+        "1.3.1-rc.1",
+        "2.0.0-rc.1",
+        "2.0.0",
+    ]
+    .iter()
+    .map(|text| version(text))
+    .collect::<Vec<_>>();
+    let select = |current: &str, req: &str| {
+        let req = VersionReq::parse(req).unwrap();
+        Version::select_update(&version(current), &req, &available).map(|v| v.to_string())
+    };
+    let some = |text: &str| Some(text.to_owned());
+
+    // The highest within the req, not the highest overall.
+    assert_eq!(select("1.2.3", "^1.2"), some("1.3.0"));
+    assert_eq!(select("1.2.3", "~1.2"), some("1.2.4"));
+    assert_eq!(select("1.2.3", "*"), some("2.0.0"));
+    assert_eq!(select("1.1.0", "<1.3.0"), some("1.2.4"));
+
+    // Nothing newer within the req.
+    assert_eq!(select("1.3.0", "^1.2"), None);
+    assert_eq!(select("1.2.4", "~1.2"), None);
+    assert_eq!(select("2.0.0", "*"), None);
+    assert_eq!(select("1.2.3", "<1.2.3"), None);
+
+    // Build metadata alone is not an update.
+    assert_eq!(select("1.2.3", "=1.2.3"), None);
+
+    // Current need not match the req itself.
+    assert_eq!(select("0.1.0", "^1.2"), some("1.3.0"));
+    assert_eq!(select("1.9.0", "^1.2"), None);
+
+    // Pre-releases only where the req allows them.
+    assert_eq!(select("1.3.0-rc.1", "~1.3.0-rc.1"), some("1.3.0"));
+    assert_eq!(select("1.2.3", ">=1.3.1-rc.1 <2"), some("1.3.1-rc.1"));
+    assert_eq!(select("1.9.0", ">=2.0.0-rc.1 <2.0.0"), some("2.0.0-rc.1"));
+    assert_eq!(select("1.9.0", "<2.0.0"), None);
+}
+
+#[test]
+fn test_with_min_prerelease() {
+    let min = version("1.2.3").with_min_prerelease();
+    assert_to_string(&min, "1.2.3-0");
+    assert_eq!(min, version("1.2.3-0"));
+    assert!(min < version("1.2.3"));
+    assert!(min > version("1.2.2"));
+    assert!(min > version("1.2.2-rc.1"));
+    assert!(min > Version::new(1, 1, u64::MAX));
+    for text in &["1.2.3-1", "1.2.3-0.0", "1.2.3-alpha", "1.2.3-A", "1.2.3--"] {
+        assert!(min < version(text), "{}", text);
+    }
+    assert_eq!(min.predecessor(), Some(version("1.2.2")));
+
+    // Replaces any pre-release and build metadata.
+    let v = version("1.2.3-rc.1+build");
+    assert_eq!(v.with_min_prerelease(), min);
+    assert_eq!(version("0.0.0").with_min_prerelease(), version("0.0.0-0"));
+}
+
+#[test]
+fn test_release_version() {
+    let release = |text: &str| version(text).release_version();
+
+    assert_eq!(release("1.2.3-rc.1"), version("1.2.3"));
+    assert_eq!(release("1.2.3-0"), version("1.2.3"));
+    assert_eq!(release("1.2.3-rc.1+build.5"), version("1.2.3"));
+    assert!(release("1.2.3-rc.1") > version("1.2.3-rc.1"));
+
+    assert_eq!(release("1.2.3"), version("1.2.3"));
+    assert_eq!(release("0.0.0"), Version::new(0, 0, 0));
+    let v = release("1.2.3+build");
+    assert_eq!(v, version("1.2.3"));
+    assert!(v.build.is_empty());
+}
+
+#[test]
+fn test_trailing_delimiter() {
+    // A `-` or `+` must be followed by at least one identifier. An empty one
+    // is an empty segment of the pre-release or build metadata, rather than
+    // the end of input, since the delimiter makes it present but empty.
+    let pre = "empty identifier segment in pre-release identifier";
+    let build = "empty identifier segment in build metadata";
+    for &(text, message, offset) in &[
+        ("1.2.3+", build, 6),
+        ("1.2.3-", pre, 6),
+        ("1.2.3-rc+", build, 9),
+        ("1.2.3-+build", pre, 6),
+    ] {
+        let err = version_err(text);
+        assert_to_string(err, message);
+
+        let errors = Version::parse_collect_errors(text).unwrap_err();
+        assert_eq!(errors.len(), 1, "{}", text);
+        assert_to_string(&errors[0].1, message);
+        assert_eq!(errors[0].0, offset, "{}", text);
+    }
+}
+
+#[test]
+fn test_prerelease_precedence_rules() {
+    let assert_lt = |a: &str, b: &str| {
+        let (va, vb) = (version(a), version(b));
+        assert!(va < vb, "expected {} < {}", a, b);
+        assert!(vb > va, "expected {} > {}", b, a);
+        assert_eq!(va.pre.cmp(&vb.pre), Ordering::Less, "{} < {}", a, b);
+    };
+
+    // 11.4.1: Identifiers consisting of only digits are compared numerically.
+    assert_lt("1.0.0-2", "1.0.0-10");
+    assert_lt("1.0.0-alpha.9", "1.0.0-alpha.10");
+    assert_lt("1.0.0-rc.1.99", "1.0.0-rc.1.100");
+    assert_lt("1.0.0-18446744073709551615", "1.0.0-18446744073709551616");
+    assert_lt("1.0.0-99999999999999999999", "1.0.0-100000000000000000000");
+
+    // 11.4.2: Identifiers with letters or hyphens are compared lexically in
+    // ASCII sort order, even where they contain digits.
+    assert_lt("1.0.0-alpha", "1.0.0-beta");
+    assert_lt("1.0.0-Beta", "1.0.0-alpha");
+    assert_lt("1.0.0-rc10", "1.0.0-rc9");
+    assert_lt("1.0.0-a-b", "1.0.0-a0");
+    assert_lt("1.0.0-10a", "1.0.0-9a");
+
+    // 11.4.3: Numeric identifiers always have lower precedence than
+    // non-numeric identifiers.
+    assert_lt("1.0.0-1", "1.0.0-alpha");
+    assert_lt("1.0.0-999", "1.0.0-a");
+    assert_lt("1.0.0-99999999999999999999", "1.0.0--");
+    assert_lt("1.0.0-2", "1.0.0-1a");
+    assert_lt("1.0.0-alpha.1", "1.0.0-alpha.beta");
+    assert_lt("1.0.0-alpha.99", "1.0.0-alpha.0a");
+
+    // 11.4.4: A larger set of fields has a higher precedence than a smaller
+    // set, if all of the preceding identifiers are equal.
+    assert_lt("1.0.0-alpha", "1.0.0-alpha.1");
+    assert_lt("1.0.0-alpha", "1.0.0-alpha.0");
+    assert_lt("1.0.0-alpha.1", "1.0.0-alpha.1.0");
+    assert_lt("1.0.0-1", "1.0.0-1.1");
+    assert_lt("1.0.0-alpha.1.beta", "1.0.0-alpha.2");
+    assert_lt("1.0.0-alpha.beta.9", "1.0.0-alpha.gamma");
+
+    // Equal identifiers, including numeric ones, have equal precedence.
+    let (a, b) = (version("1.0.0-alpha.1.rc"), version("1.0.0-alpha.1.rc"));
+    assert_eq!(a.pre.cmp(&b.pre), Ordering::Equal);
+}
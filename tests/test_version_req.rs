@@ -9,8 +9,14 @@ mod node;
 mod util;
 
 use crate::util::*;
+use semver::{
+    Comparator, IncludeBoundsMode, MatchReason, Op, TokenKind, VersionRange, VersionReqParser,
+};
+use std::cmp::Ordering;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::ops::{Bound, Range, RangeInclusive};
+use std::u64;
 
 #[cfg(test_node_semver)]
 use node::{req, VersionReq};
@@ -103,6 +109,17 @@ fn test_exact() {
     let ref r = req("=0.1.0+meta");
     assert_to_string(r, "=0.1.0");
     assert_match_all(r, &["0.1.0", "0.1.0+meta", "0.1.0+any"]);
+
+    // The exact pre-release is allowed through the pre-release gate, but no
+    // other pre-release of the same version, nor the release itself.
+    let ref r = req("=1.2.3-rc.1");
+    assert_to_string(r, "=1.2.3-rc.1");
+    assert_match_all(r, &["1.2.3-rc.1", "1.2.3-rc.1+build"]);
+    assert_match_none(r, &["1.2.3-rc.2", "1.2.3-rc", "1.2.3", "1.2.4-rc.1"]);
+
+    let ref r = req("=1.2.3-rc.1 || =2.0.0");
+    assert_match_all(r, &["1.2.3-rc.1", "2.0.0"]);
+    assert_match_none(r, &["1.2.3-rc.2", "1.2.3", "2.0.0-rc.1"]);
 }
 
 #[test]
@@ -417,6 +434,43 @@ fn test_digit_after_wildcard() {
     assert_to_string(err, "unexpected character after wildcard in version req");
 }
 
+#[test]
+fn test_wildcard_must_be_last() {
+    for s in &[
+        "*",
+        "1.*",
+        "1.*.*",
+        "1.2.*",
+        "1.x",
+        "1.x.X",
+        ">=1.*",
+        "1.x-2.x",
+        "1.*-2",
+        "1.x-2",
+        "1.2.x-1.3.x",
+    ] {
+        req(s);
+    }
+    assert_to_string(req("1.x-2.x"), "1.* - 2.*");
+
+    for s in &[
+        "1.*.3",
+        "1.x.0",
+        "*.*",
+        "*.1.2",
+        "1.2.*.3",
+        "1.*.*.*",
+        "1.2.*-rc",
+        "1.*+build",
+        "1.2.*3",
+        "1.*3",
+        ">=1.x0",
+    ] {
+        let err = req_err(s);
+        assert_to_string(err, "unexpected character after wildcard in version req");
+    }
+}
+
 #[test]
 fn test_eq_hash() {
     fn calculate_hash(value: impl Hash) -> u64 {
@@ -470,3 +524,1817 @@ fn test_wildcard_and_another() {
         "wildcard req (*) must be the only comparator in the version req",
     );
 }
+
+#[test]
+fn test_exclusive_upper_bound() {
+    let upper = |text: &str| req(text).exclusive_upper_bound();
+
+    assert_eq!(upper("^1.2.3"), Some(version("2.0.0")));
+    assert_eq!(upper("^0.2.3"), Some(version("0.3.0")));
+    assert_eq!(upper("^0.0.3"), Some(version("0.0.4")));
+    assert_eq!(upper("^1"), Some(version("2.0.0")));
+    assert_eq!(upper("~1.2"), Some(version("1.3.0")));
+    assert_eq!(upper("~1.2.3"), Some(version("1.3.0")));
+    assert_eq!(upper("1.2.*"), Some(version("1.3.0")));
+    assert_eq!(upper("<1.5"), Some(version("1.5.0")));
+    assert_eq!(upper("<=1.5"), Some(version("1.6.0")));
+    assert_eq!(upper(">=1 <1.8.0"), Some(version("1.8.0")));
+    assert_eq!(upper("1.0.0 - 2.3"), Some(version("2.4.0")));
+
+    // Inclusive upper end.
+    assert_eq!(upper("<=1.2.3"), Some(version("1.2.4-0")));
+    assert_eq!(upper("=1.2.3-rc.1"), Some(version("1.2.3-rc.1.0")));
+
+    // Open above.
+    assert_eq!(upper(">=1"), None);
+    assert_eq!(upper(">1.2.3"), None);
+    assert_eq!(upper("*"), None);
+
+    // More than one alternative.
+    assert_eq!(upper("^1 || ^2"), None);
+
+    let max = u64::MAX;
+    assert_eq!(upper(&format!("^{}", max)), None);
+    assert_eq!(upper(&format!("~1.{}", max)), Some(version("2.0.0")));
+}
+
+#[test]
+fn test_difference() {
+    let diff = |a: &str, b: &str| req(a).difference(&req(b)).to_string();
+
+    assert_eq!(diff(">=1 <4", "^2"), ">=1.0.0 <2.0.0 || >=3.0.0 <4.0.0");
+    assert_eq!(diff(">=1 <3", "^2"), ">=1.0.0 <2.0.0");
+    assert_eq!(diff("^1", "<1.5"), ">=1.5.0 <2.0.0");
+    assert_eq!(diff("^1", ">=1.5"), ">=1.0.0 <1.5.0");
+    assert_eq!(diff("^1", "=1.2.3"), ">=1.0.0 <1.2.3 || >1.2.3 <2.0.0");
+    assert_eq!(diff("*", "^1"), "<1.0.0 || >=2.0.0");
+    assert_eq!(
+        diff("^1 || ^3", "^1.5 || ^3.2"),
+        ">=1.0.0 <1.5.0 || >=3.0.0 <3.2.0"
+    );
+    assert_eq!(diff("^1", "^2"), ">=1.0.0 <2.0.0");
+    assert_eq!(diff("=1.2.3", "<=1.2.3"), "<0.0.0");
+    assert_eq!(diff("^1", "*"), "<0.0.0");
+    assert_eq!(diff("<=1.2.3", "<1.2.3"), "=1.2.3");
+
+    let versions = [
+        "0.0.0", "0.9.9", "1.0.0", "1.2.2", "1.2.3", "1.2.4", "1.4.9", "1.5.0", "1.9.9", "2.0.0",
+        "2.5.0", "3.0.0", "3.1.9", "3.2.0", "4.0.0", "9.9.9",
+    ];
+    let reqs = [
+        "*",
+        "^1",
+        "^1.5",
+        "^2",
+        "~1.2",
+        "=1.2.3",
+        "<1.5",
+        ">=1.5",
+        ">1.2.3",
+        "<=3.1",
+        "1.2.3 - 3.1",
+        "^1 || ^3",
+        "^1.5 || ^3.2",
+    ];
+    for a in &reqs {
+        for b in &reqs {
+            let (a, b) = (req(a), req(b));
+            let diff = a.difference(&b);
+            for v in &versions {
+                let v = version(v);
+                let expected = a.matches(&v) && !b.matches(&v);
+                assert_eq!(diff.matches(&v), expected, "{} - {} @ {}", a, b, v);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_op_from_str() {
+    assert_eq!("=".parse::<Op>().unwrap(), Op::Exact);
+    assert_eq!(">".parse::<Op>().unwrap(), Op::Greater);
+    assert_eq!(">=".parse::<Op>().unwrap(), Op::GreaterEq);
+    assert_eq!("<".parse::<Op>().unwrap(), Op::Less);
+    assert_eq!("<=".parse::<Op>().unwrap(), Op::LessEq);
+    assert_eq!("~".parse::<Op>().unwrap(), Op::Tilde);
+    assert_eq!("^".parse::<Op>().unwrap(), Op::Caret);
+    assert_eq!("*".parse::<Op>().unwrap(), Op::Wildcard);
+    assert_eq!("~>".parse::<Op>().unwrap(), Op::Pessimistic);
+
+    for s in &["==", "", " >=", ">= ", "=>", "~=", "^1", "x"] {
+        let err = s.parse::<Op>().unwrap_err();
+        assert_to_string(err, "unrecognized comparison operator");
+    }
+}
+
+#[test]
+fn test_intersects_interval() {
+    let ref r = req("^1.2.3");
+    let (v1, v2, v3) = (version("1.0.0"), version("1.2.3"), version("2.0.0"));
+
+    // Disjoint.
+    assert!(!r.intersects_interval(Bound::Unbounded, Bound::Excluded(&v2)));
+    assert!(!r.intersects_interval(Bound::Included(&v3), Bound::Unbounded));
+    assert!(!r.intersects_interval(Bound::Included(&v1), Bound::Included(&version("1.2.2"))));
+
+    // Touching.
+    assert!(r.intersects_interval(Bound::Unbounded, Bound::Included(&v2)));
+    assert!(r.intersects_interval(Bound::Included(&v1), Bound::Included(&v2)));
+    assert!(!r.intersects_interval(Bound::Excluded(&v1), Bound::Excluded(&v2)));
+    assert!(!r.intersects_interval(Bound::Included(&v3), Bound::Included(&v3)));
+
+    // Overlapping.
+    assert!(r.intersects_interval(Bound::Included(&v1), Bound::Included(&v3)));
+    assert!(r.intersects_interval(Bound::Excluded(&v2), Bound::Unbounded));
+    assert!(r.intersects_interval(Bound::Unbounded, Bound::Unbounded));
+    let (lo, hi) = (version("1.5.0"), version("1.6.0"));
+    assert!(r.intersects_interval(Bound::Included(&lo), Bound::Excluded(&hi)));
+
+    // Empty interval.
+    assert!(!r.intersects_interval(Bound::Included(&hi), Bound::Excluded(&lo)));
+
+    let ref r = req("<1.0.0 || >=2.0.0");
+    assert!(!r.intersects_interval(Bound::Included(&v1), Bound::Excluded(&v3)));
+    assert!(r.intersects_interval(Bound::Included(&v1), Bound::Included(&v3)));
+}
+
+#[test]
+fn test_parse_no_prerelease() {
+    for text in &[
+        "^1.2.3",
+        ">=1.0.0 <2.0.0",
+        "1.0.0 - 2.0.0",
+        "*",
+        "~1 || 3.x",
+    ] {
+        let req = semver::VersionReq::parse_no_prerelease(text).unwrap();
+        assert_eq!(req, semver::VersionReq::parse(text).unwrap());
+    }
+
+    for &(text, offset) in &[
+        ("^1.2.3-rc.1", 6),
+        (">=1.0.0 <2.0.0-0", 14),
+        ("1.0.0 - 2.0.0-beta", 13),
+        ("^1 || =2.0.0-rc", 12),
+        ("1.2.3+build-5 || 2.0.0-rc", 22),
+    ] {
+        let err = semver::VersionReq::parse_no_prerelease(text).unwrap_err();
+        let message = format!(
+            "pre-release at byte {} is not allowed in this version req",
+            offset,
+        );
+        assert_to_string(err, &message);
+    }
+
+    // Other errors are reported as usual.
+    let err = semver::VersionReq::parse_no_prerelease("^1.2.3-rc.01").unwrap_err();
+    assert_to_string(err, "invalid leading zero in pre-release identifier");
+}
+
+#[test]
+fn test_parse_verbose() {
+    fn tokens(text: &str) -> Vec<(&str, TokenKind)> {
+        let (req, tokens) = semver::VersionReq::parse_verbose(text).unwrap();
+        assert_eq!(req, semver::VersionReq::parse(text).unwrap());
+        tokens
+            .into_iter()
+            .map(|(span, kind)| (&text[span], kind))
+            .collect()
+    }
+
+    let (_req, spans) = semver::VersionReq::parse_verbose("^1.2.3 || ~2.0").unwrap();
+    assert_eq!(
+        spans,
+        [
+            (0..1, TokenKind::Operator),
+            (1..2, TokenKind::Major),
+            (3..4, TokenKind::Minor),
+            (5..6, TokenKind::Patch),
+            (7..9, TokenKind::Or),
+            (10..11, TokenKind::Operator),
+            (11..12, TokenKind::Major),
+            (13..14, TokenKind::Minor),
+        ],
+    );
+
+    assert_eq!(
+        tokens(" >= 10.20.30-rc.1+build-5 <11"),
+        [
+            (">=", TokenKind::Operator),
+            ("10", TokenKind::Major),
+            ("20", TokenKind::Minor),
+            ("30", TokenKind::Patch),
+            ("rc.1", TokenKind::Pre),
+            ("build-5", TokenKind::Build),
+            ("<", TokenKind::Operator),
+            ("11", TokenKind::Major),
+        ],
+    );
+    assert_eq!(
+        tokens("1.2.3-beta - 2.x||3"),
+        [
+            ("1", TokenKind::Major),
+            ("2", TokenKind::Minor),
+            ("3", TokenKind::Patch),
+            ("beta", TokenKind::Pre),
+            ("-", TokenKind::Hyphen),
+            ("2", TokenKind::Major),
+            ("x", TokenKind::Minor),
+            ("||", TokenKind::Or),
+            ("3", TokenKind::Major),
+        ],
+    );
+    assert_eq!(tokens(" * "), [("*", TokenKind::Major)]);
+    assert_eq!(tokens(""), []);
+    assert_eq!(tokens("latest"), [("latest", TokenKind::Major)]);
+    assert_eq!(tokens(" LATEST "), [("LATEST", TokenKind::Major)]);
+
+    let err = semver::VersionReq::parse_verbose("^1.2.3 || ~2.q").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected character 'q' while parsing minor version number",
+    );
+}
+
+#[test]
+fn test_parse_collect_errors() {
+    let errors = semver::VersionReq::parse_collect_errors("^1.q || ~2 || >=3.0.y").unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_to_string(
+        &errors[0].1,
+        "unexpected character 'q' while parsing minor version number",
+    );
+    assert_eq!(errors[0].0, 0);
+    assert_eq!(errors[1].0, 14);
+
+    let errors = semver::VersionReq::parse_collect_errors("~2 ||  <1.x.y").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 7);
+
+    // Only the first alternative may be `*`, `latest`, or empty.
+    for &(text, offset) in &[
+        ("1.0 || ", 7),
+        ("^1.2 || latest", 8),
+        ("^1 || *", 6),
+        ("^1 || || ^2", 6),
+    ] {
+        let errors = semver::VersionReq::parse_collect_errors(text).unwrap_err();
+        assert_eq!(errors.len(), 1, "{}", text);
+        assert_eq!(errors[0].0, offset, "{}", text);
+    }
+    let errors = semver::VersionReq::parse_collect_errors("* || 1").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 0);
+
+    // A first alternative that is valid on its own as a whole req is not
+    // reported.
+    for &(text, offset) in &[
+        ("* || 1.q", 5),
+        ("latest || 1.q", 10),
+        ("LATEST ||  1.q", 11),
+    ] {
+        let errors = semver::VersionReq::parse_collect_errors(text).unwrap_err();
+        assert_eq!(errors.len(), 1, "{}", text);
+        assert_to_string(
+            &errors[0].1,
+            "unexpected character 'q' while parsing minor version number",
+        );
+        assert_eq!(errors[0].0, offset, "{}", text);
+    }
+
+    let errors = semver::VersionReq::parse_collect_errors("1.q || *").unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, 0);
+    assert_eq!(errors[1].0, 7);
+
+    let r = semver::VersionReq::parse_collect_errors("^1.2 || ~2").unwrap();
+    assert_eq!(r, semver::VersionReq::parse("^1.2 || ~2").unwrap());
+}
+
+#[test]
+fn test_union_all() {
+    let union = |texts: &[&str]| {
+        semver::VersionReq::union_all(
+            texts
+                .iter()
+                .map(|text| semver::VersionReq::parse(text).unwrap()),
+        )
+    };
+
+    let r = union(&["^1.2", "~3.1 || =4.0.0", "<0.1.0 >=0.0.5"]);
+    assert_eq!(
+        r,
+        semver::VersionReq::parse("^1.2 || ~3.1 || =4.0.0 || <0.1.0 >=0.0.5").unwrap()
+    );
+    let ref r = req(&r.to_string());
+    assert_match_all(r, &["1.2.0", "1.9.0", "3.1.5", "4.0.0", "0.0.7"]);
+    assert_match_none(r, &["1.1.0", "2.0.0", "3.2.0", "4.0.1", "0.1.0"]);
+
+    // A pre-release comparator in one req allows matching pre-releases of the
+    // same major.minor.patch through any of the others, as with `||`.
+    let ref r = req(&union(&[">=1.0.0", "=2.0.0-rc.1"]).to_string());
+    assert_match_all(r, &["2.0.0-rc.1", "2.0.0-rc.2"]);
+
+    // Identity of a single req.
+    assert_eq!(
+        union(&["^1.2.3"]),
+        semver::VersionReq::parse("^1.2.3").unwrap()
+    );
+
+    // `*` matches every release, but the other reqs still allow their
+    // pre-releases.
+    assert_to_string(union(&["^1", "*", "^3"]), "^1 || >=0.0.0 || ^3");
+    assert_eq!(union(&["*"]), semver::VersionReq::STAR);
+    assert_eq!(union(&[""]), semver::VersionReq::STAR);
+    let ref r = req(&union(&[">=1.2.3-rc.1", "*"]).to_string());
+    assert_match_all(r, &["1.2.3-rc.1", "1.2.3-rc.2", "0.0.0", "5.0.0"]);
+    assert_match_none(r, &["1.2.4-rc.1"]);
+
+    // A union matches whatever any of its reqs matches, and possibly more
+    // pre-releases, as above.
+    let texts = [
+        "*",
+        ">=1.2.3-rc.1",
+        "<2.0.0-0",
+        "^1 <1.5.0",
+        "=2.0.0-beta.2",
+        "1.0.0 - 1.2.3-rc.2",
+        "<0.0.0",
+    ];
+    let versions = [
+        "0.0.0",
+        "1.0.0",
+        "1.2.3-rc.1",
+        "1.2.3-rc.2",
+        "1.9.0",
+        "2.0.0-beta.2",
+    ];
+    for a in &texts {
+        for b in &texts {
+            let r = union(&[a, b]);
+            for v in &versions {
+                let v = semver::Version::parse(v).unwrap();
+                let a_matches = semver::VersionReq::parse(a).unwrap().matches(&v);
+                let b_matches = semver::VersionReq::parse(b).unwrap().matches(&v);
+                if a_matches || b_matches {
+                    assert!(r.matches(&v), "{} || {} vs {}", a, b, v);
+                }
+            }
+        }
+    }
+
+    // The empty union matches nothing.
+    let ref r = req(&union(&[]).to_string());
+    assert_to_string(r, "<0.0.0");
+    assert_match_none(
+        r,
+        &["0.0.0", "0.0.0-0", "1.0.0", "18446744073709551615.0.0"],
+    );
+}
+
+#[test]
+fn test_caret_wildcard() {
+    let ref r = req("^1.x");
+    assert_to_string(r, "^1");
+    assert_match_all(r, &["1.0.0", "1.2.3", "1.99.99"]);
+    assert_match_none(r, &["0.9.9", "2.0.0", "1.2.3-rc"]);
+
+    let ref r = req("^1.x.x");
+    assert_to_string(r, "^1");
+
+    let ref r = req("^1.2.x");
+    assert_to_string(r, "^1.2");
+    assert_match_all(r, &["1.2.0", "1.2.9", "1.9.0"]);
+    assert_match_none(r, &["1.1.9", "2.0.0"]);
+
+    let ref r = req("^1.2.*");
+    assert_to_string(r, "^1.2");
+
+    let ref r = req("^0.x");
+    assert_match_all(r, &["0.0.0", "0.1.0", "0.9.9"]);
+    assert_match_none(r, &["1.0.0"]);
+
+    let ref r = req("^0.0.x");
+    assert_match_all(r, &["0.0.0", "0.0.9"]);
+    assert_match_none(r, &["0.1.0"]);
+
+    let err = req_err("^1.x.3");
+    assert_to_string(err, "unexpected character after wildcard in version req");
+}
+
+#[test]
+fn test_partial_comparison() {
+    let ref r = req(">=1.2");
+    assert_match_all(r, &["1.2.0", "1.2.5", "1.3.0", "2.0.0"]);
+    assert_match_none(r, &["1.1.9", "1.2.5-rc.1"]);
+
+    let ref r = req(">1.2");
+    assert_match_all(r, &["1.3.0", "2.0.0"]);
+    assert_match_none(r, &["1.2.0", "1.2.5"]);
+
+    let ref r = req("<=1.2");
+    assert_match_all(r, &["1.1.9", "1.2.0", "1.2.5"]);
+    assert_match_none(r, &["1.3.0", "1.3.0-rc.1"]);
+
+    let ref r = req("<1.2");
+    assert_match_all(r, &["1.1.9"]);
+    assert_match_none(r, &["1.2.0", "1.2.5"]);
+
+    let ref r = req(">=1");
+    assert_match_all(r, &["1.0.0", "1.9.5", "2.0.0"]);
+    assert_match_none(r, &["0.9.9"]);
+
+    let ref r = req(">1");
+    assert_match_all(r, &["2.0.0"]);
+    assert_match_none(r, &["1.0.0", "1.9.5"]);
+}
+
+#[test]
+fn test_comparison_wildcard() {
+    let to_string = |text: &str| semver::VersionReq::parse(text).unwrap().to_string();
+
+    assert_eq!(to_string(">=1.x"), ">=1");
+    assert_eq!(to_string(">=1.x.x"), ">=1");
+    assert_eq!(to_string("<2.x"), "<2");
+    assert_eq!(to_string("<=1.2.x"), "<=1.2");
+    assert_eq!(to_string(">1.2.*"), ">1.2");
+    assert_eq!(to_string("=1.x"), "=1");
+
+    let ref r = req(">=1.x");
+    assert_match_all(r, &["1.0.0", "1.2.3", "2.0.0"]);
+    assert_match_none(r, &["0.9.9", "1.0.0-rc.1"]);
+
+    let ref r = req("<2.x");
+    assert_match_all(r, &["0.0.0", "1.9.9"]);
+    assert_match_none(r, &["2.0.0", "2.0.1"]);
+
+    // Everything in 1.2, so the same as <1.3.0.
+    let ref r = req("<=1.2.x");
+    assert_match_all(r, &["0.9.9", "1.2.0", "1.2.99"]);
+    assert_match_none(r, &["1.3.0", "1.3.0-rc.1", "2.0.0"]);
+
+    let ref r = req(">1.2.x");
+    assert_match_all(r, &["1.3.0", "2.0.0"]);
+    assert_match_none(r, &["1.2.0", "1.2.99"]);
+
+    let ref r = req("<1.2.x");
+    assert_match_all(r, &["1.1.99"]);
+    assert_match_none(r, &["1.2.0", "1.2.99"]);
+
+    let err = semver::VersionReq::parse(">=*").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected character '*' while parsing major version number",
+    );
+
+    let err = semver::VersionReq::parse("<x").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected character 'x' while parsing major version number",
+    );
+}
+
+#[test]
+fn test_caret_without_version() {
+    let err = req_err("^");
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing major version number",
+    );
+
+    let err = req_err("^ ");
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing major version number",
+    );
+
+    let err = req_err("^*");
+    assert_to_string(
+        err,
+        "unexpected character '*' while parsing major version number",
+    );
+
+    let err = req_err("^x");
+    assert_to_string(
+        err,
+        "unexpected character 'x' while parsing major version number",
+    );
+
+    let err = req_err("^X.1");
+    assert_to_string(
+        err,
+        "unexpected character 'X' while parsing major version number",
+    );
+
+    let err = req_err(">=1.0.0 ^");
+    assert_to_string(
+        err,
+        "unexpected end of input while parsing major version number",
+    );
+}
+
+#[test]
+fn test_comparator_negate() {
+    let negate = |text: &str| Comparator::parse(text).unwrap().negate().to_string();
+
+    assert_eq!(negate("=1.2.3"), "<1.2.3 || >1.2.3");
+    assert_eq!(negate("=1.2"), "<1.2.0 || >=1.3.0");
+    assert_eq!(negate("=1"), "<1.0.0 || >=2.0.0");
+    assert_eq!(negate(">1.2.3"), "<=1.2.3");
+    assert_eq!(negate(">1.2"), "<1.3.0");
+    assert_eq!(negate(">=1.2.3"), "<1.2.3");
+    assert_eq!(negate("<1.2.3"), ">=1.2.3");
+    assert_eq!(negate("<=1.2.3"), ">1.2.3");
+    assert_eq!(negate("<=1.2"), ">=1.3.0");
+    assert_eq!(negate("~1.2.3"), "<1.2.3 || >=1.3.0");
+    assert_eq!(negate("~1"), "<1.0.0 || >=2.0.0");
+    assert_eq!(negate("^1.2.3"), "<1.2.3 || >=2.0.0");
+    assert_eq!(negate("^0.2.3"), "<0.2.3 || >=0.3.0");
+    assert_eq!(negate("^0.0.3"), "<0.0.3 || >=0.0.4");
+    assert_eq!(negate("1.2.*"), "<1.2.0 || >=1.3.0");
+    assert_eq!(negate(">=0.0.0"), "<0.0.0");
+    assert_eq!(negate("<0.0.0"), ">=0.0.0");
+    assert_eq!(negate(">=1.2.3-rc.1"), "<1.2.3-rc.1");
+
+    let versions = [
+        "0.0.0", "0.0.3", "0.0.4", "0.2.3", "0.3.0", "1.0.0", "1.2.2", "1.2.3", "1.2.4", "1.3.0",
+        "1.9.9", "2.0.0", "9.0.0",
+    ];
+    let comparators = [
+        "=1.2.3", "=1.2", ">1.2.3", ">1.2", ">=1.2.3", "<1.2.3", "<1.2", "<=1.2.3", "<=1.2",
+        "~1.2.3", "~1.2", "^1.2.3", "^0.2.3", "^0.0.3", "^0.0", "^0", "1.*", "1.2.*",
+    ];
+    for cmp in &comparators {
+        let cmp = Comparator::parse(cmp).unwrap();
+        let negated = cmp.negate();
+        for ver in &versions {
+            let ver = version(ver);
+            assert_ne!(cmp.matches(&ver), negated.matches(&ver), "{} {}", cmp, ver);
+        }
+    }
+
+    // Pre-releases follow the usual rule on both sides.
+    let ref ver = version("1.2.3-rc.1");
+    let cmp = Comparator::parse(">=1.2.3").unwrap();
+    assert!(!cmp.matches(ver));
+    assert!(!cmp.negate().matches(ver));
+    let cmp = Comparator::parse(">=1.2.3-rc.1").unwrap();
+    assert!(cmp.matches(ver));
+    assert!(!cmp.negate().matches(ver));
+    assert!(cmp.negate().matches(&version("1.2.3-rc.0")));
+}
+
+#[test]
+fn test_pessimistic() {
+    // node-semver reads `~>` as `~`, so these are checked against this crate
+    // only.
+    let req = |text: &str| semver::VersionReq::parse(text).unwrap();
+    let matches_all = |r: &semver::VersionReq, versions: &[&str]| {
+        for text in versions {
+            assert!(r.matches(&version(text)), "{} {}", r, text);
+        }
+    };
+    let matches_none = |r: &semver::VersionReq, versions: &[&str]| {
+        for text in versions {
+            assert!(!r.matches(&version(text)), "{} {}", r, text);
+        }
+    };
+
+    let ref r = req("~>1.2");
+    assert_to_string(r, "~>1.2");
+    matches_all(r, &["1.2.0", "1.2.9", "1.3.0", "1.99.0"]);
+    matches_none(r, &["1.1.9", "2.0.0", "0.2.0", "1.3.0-rc"]);
+
+    let ref r = req("~>1.2.3");
+    assert_to_string(r, "~>1.2.3");
+    matches_all(r, &["1.2.3", "1.2.4", "1.2.99"]);
+    matches_none(r, &["1.2.2", "1.3.0", "2.0.0", "1.2.4-rc"]);
+
+    let ref r = req("~>1");
+    matches_all(r, &["1.0.0", "1.9.9"]);
+    matches_none(r, &["0.9.9", "2.0.0"]);
+
+    // Unlike `^`, `~>` treats a major version of 0 like any other.
+    let ref r = req("~>0.2");
+    matches_all(r, &["0.2.0", "0.3.0", "0.99.0"]);
+    matches_none(r, &["0.1.9", "1.0.0"]);
+
+    let ref r = req("~> 1.2.3-rc.1");
+    assert_to_string(r, "~>1.2.3-rc.1");
+    matches_all(r, &["1.2.3-rc.1", "1.2.3-rc.2", "1.2.3", "1.2.9"]);
+    matches_none(r, &["1.2.3-beta", "1.3.0"]);
+
+    let ref r = req("~>1.2 <1.5");
+    matches_all(r, &["1.2.0", "1.4.9"]);
+    matches_none(r, &["1.5.0"]);
+
+    assert_eq!("~>".parse::<Op>().unwrap(), Op::Pessimistic);
+    assert_eq!(req("~>1.2").exclusive_upper_bound(), Some(version("2.0.0")));
+    assert_eq!(
+        req("~>1.2.3").exclusive_upper_bound(),
+        Some(version("1.3.0"))
+    );
+}
+
+#[test]
+fn test_complement() {
+    let complement = |text: &str| req(text).complement().to_string();
+
+    assert_eq!(
+        complement("^1.2 || ^3"),
+        "<1.2.0 || >=2.0.0 <3.0.0 || >=4.0.0"
+    );
+    assert_eq!(complement(">=1.0.0"), "<1.0.0");
+    assert_eq!(complement("<1.0.0 || >=2.0.0"), ">=1.0.0 <2.0.0");
+    assert_eq!(complement("=1.2.3"), "<1.2.3 || >1.2.3");
+    assert_eq!(complement("1.0.0 - 2.0.0"), "<1.0.0 || >2.0.0");
+    assert_eq!(complement("*"), "<0.0.0");
+    assert_eq!(complement("<0.0.0"), ">=0.0.0");
+    assert_eq!(complement("^1 || ^1.5"), "<1.0.0 || >=2.0.0");
+
+    let reqs = [
+        "*",
+        "^1.2.3",
+        "~1.2 || ^3",
+        ">=1.0.0 <1.5.0 || >=2.0.0 <2.5.0",
+        "0.1.0 - 0.3.5",
+        "=1.2.3 || =1.2.5",
+        ">1.2 <=1.4",
+        "^0.0.3 || ^0.2",
+        "1.x || 3.1.*",
+        "~>1.2",
+        "<0.0.0",
+    ];
+    let mut state = Xorshift::with_seed(0x9e37_79b9_7f4a_7c15);
+    let mut random = || state.next_u64() % 6;
+    for text in &reqs {
+        let r = semver::VersionReq::parse(text).unwrap();
+        let c = r.complement();
+        for _ in 0..500 {
+            let ver = semver::Version::new(random(), random(), random());
+            assert_ne!(r.matches(&ver), c.matches(&ver), "{} {}", r, ver);
+        }
+    }
+
+    // A pre-release can be matched by neither.
+    let ref ver = version("1.5.0-rc.1");
+    assert!(!req("^1.2.3").matches(ver));
+    assert!(!req("^1.2.3").complement().matches(ver));
+}
+
+#[test]
+fn test_pre_other_release() {
+    // A pre-release matches only if some comparator has the same
+    // major.minor.patch and a pre-release of its own, even when the
+    // pre-release is numerically within the range. This agrees with
+    // node-semver's satisfies without includePrerelease.
+    let ref r = req(">1.2.2");
+    assert_match_all(r, &["1.2.3", "1.3.0"]);
+    assert_match_none(r, &["1.2.3-rc.1", "1.3.0-0"]);
+
+    let ref r = req(">=1.2.3");
+    assert_match_none(r, &["1.2.3-rc.1"]);
+
+    let ref r = req("<1.2.3");
+    assert_match_none(r, &["1.2.3-rc.1", "1.2.2-rc.1"]);
+
+    // The pre-release on the comparator has to be on the same
+    // major.minor.patch as the version.
+    let ref r = req(">1.2.2-0");
+    assert_match_all(r, &["1.2.2-1", "1.2.2", "1.2.3"]);
+    assert_match_none(r, &["1.2.3-rc.1"]);
+
+    let ref r = req(">=1.2.3-0");
+    assert_match_all(r, &["1.2.3-0", "1.2.3-rc.1", "1.2.3"]);
+    assert_match_none(r, &["1.2.4-rc.1"]);
+
+    // Any comparator in the req can allow it, in any of the ranges.
+    let ref r = req(">1.2.2 <1.2.3-rc.5");
+    assert_match_all(r, &["1.2.3-rc.1"]);
+    assert_match_none(r, &["1.2.3-rc.5"]);
+}
+
+#[test]
+fn test_from_ranges() {
+    let cmp = |text: &str| Comparator::parse(text).unwrap();
+
+    let r = semver::VersionReq::from_ranges(vec![
+        VersionRange::Simple(cmp("^1.2")),
+        VersionRange::Hyphen(cmp("3.0.0"), cmp("3.4")),
+        VersionRange::Intersection(vec![cmp(">=5.0.0"), cmp("<5.2.0")]),
+    ])
+    .unwrap();
+    assert_eq!(
+        r,
+        semver::VersionReq::parse("^1.2 || 3.0.0 - 3.4 || >=5.0.0 <5.2.0").unwrap()
+    );
+    let ref r = req(&r.to_string());
+    assert_match_all(r, &["1.2.0", "1.9.9", "3.0.0", "3.4.9", "5.1.0"]);
+    assert_match_none(r, &["1.1.0", "2.0.0", "3.5.0", "5.2.0"]);
+
+    let r = semver::VersionReq::from_ranges(Vec::new()).unwrap();
+    assert_eq!(r, semver::VersionReq::STAR);
+
+    // Degenerate but not inverted.
+    for (left, right) in &[("1.2.3", "1.2.3"), ("1.2", "1.2.0"), ("1.2.3-rc", "1.2.3")] {
+        let range = VersionRange::Hyphen(cmp(left), cmp(right));
+        assert!(semver::VersionReq::from_ranges(vec![range]).is_ok());
+    }
+
+    for (left, right) in &[("2.0.0", "1.0.0"), ("1.2.3", "1.2.3-rc"), ("1.3", "1.2")] {
+        let ranges = vec![
+            VersionRange::Simple(cmp("^1")),
+            VersionRange::Hyphen(cmp(left), cmp(right)),
+        ];
+        let err = semver::VersionReq::from_ranges(ranges).unwrap_err();
+        assert_to_string(err, "hyphen range lower bound is above its upper bound");
+    }
+
+    for (left, right) in &[(">1.0.0", "2.0.0"), ("1.0.0", "<2"), ("~1", "^2")] {
+        let range = VersionRange::Hyphen(cmp(left), cmp(right));
+        let err = semver::VersionReq::from_ranges(vec![range]).unwrap_err();
+        assert_to_string(err, "comparison operator is not allowed in a hyphen range");
+    }
+}
+
+#[test]
+fn test_hyphen_range() {
+    let ref r = req("1.2.3 - 2.3.4");
+    assert_to_string(r, "=1.2.3 - =2.3.4");
+    assert_match_all(r, &["1.2.3", "1.9.0", "2.0.0", "2.3.4", "2.3.4+build"]);
+    assert_match_none(r, &["1.2.2", "2.3.5", "3.0.0", "2.3.4-rc.1"]);
+
+    // The displayed form, with explicit `=`, and wildcards parse the same as
+    // plain versions.
+    assert_eq!(req("=1.2.3 - =2.3.4"), req("1.2.3 - 2.3.4"));
+    let ref r = req("1.x - 2.*");
+    assert_match_all(r, &["1.0.0", "2.9.9"]);
+    assert_match_none(r, &["0.9.9", "3.0.0"]);
+
+    for text in &[
+        ">1.2.3 - 2.3.4",
+        "1.2.3 - <2.3.4",
+        "~1.2.3 - ^2",
+        "1 - >=2 || ^3",
+    ] {
+        let err = semver::VersionReq::parse(text).unwrap_err();
+        assert_to_string(err, "comparison operator is not allowed in a hyphen range");
+    }
+}
+
+#[test]
+fn test_greater_eq_zero() {
+    for text in &[">=0.0.0", ">=0.0", ">=0", ">= 0.0.0", " >=0.0.0 "] {
+        let r = semver::VersionReq::parse(text).unwrap();
+        assert_eq!(r, semver::VersionReq::STAR, "{}", text);
+        assert_to_string(&r, "*");
+    }
+
+    // Like `*`, these match every release but no pre-release.
+    let ref r = req(">=0.0.0");
+    assert_match_all(r, &["0.0.0", "0.1.0", "1.0.0", "18446744073709551615.0.0"]);
+    assert_match_none(r, &["0.0.0-0", "0.0.1-rc", "1.0.0-rc"]);
+
+    // Not equivalent to `*`.
+    for text in &[
+        ">=0.0.0-0",
+        ">0.0.0",
+        ">=0.0.1",
+        ">=0.0.0 <1.0.0",
+        ">=0.0.0 || ^1.2.3-rc",
+    ] {
+        let r = semver::VersionReq::parse(text).unwrap();
+        assert_ne!(r, semver::VersionReq::STAR, "{}", text);
+    }
+    let ref r = req(">=0.0.0-0");
+    assert_match_all(r, &["0.0.0-0", "0.0.0"]);
+}
+
+#[test]
+fn test_latest() {
+    for text in &["latest", "LATEST", "Latest", " latest ", "*", "", "  "] {
+        let r = semver::VersionReq::parse(text).unwrap();
+        assert_eq!(r, semver::VersionReq::STAR, "{:?}", text);
+    }
+
+    let ref r = req("latest");
+    assert_to_string(r, "*");
+    assert_match_all(r, &["0.0.0", "1.2.3", "99.0.0"]);
+    assert_match_none(r, &["1.2.3-rc.1"]);
+
+    for text in &[
+        "lateest",
+        "latest.1",
+        "latest || ^1",
+        "^1 || latest",
+        "late st",
+    ] {
+        req_err(text);
+    }
+    let err = req_err("lateest");
+    assert_to_string(
+        err,
+        "unexpected character 'l' while parsing major version number",
+    );
+}
+
+#[test]
+fn test_pre_zero() {
+    // 1.0.0-0 is the lowest possible pre-release of 1.0.0: a numeric
+    // identifier is less than any non-numeric one, and 0 is the least number.
+    assert!(version("1.0.0-0") < version("1.0.0-0.0"));
+    assert!(version("1.0.0-0") < version("1.0.0-1"));
+    assert!(version("1.0.0-0") < version("1.0.0-alpha"));
+    assert!(version("1.0.0-0") < version("1.0.0-0alpha"));
+
+    let ref r = req(">=1.0.0-0");
+    assert_match_all(
+        r,
+        &[
+            "1.0.0-0",
+            "1.0.0-0.0",
+            "1.0.0-1",
+            "1.0.0-alpha",
+            "1.0.0",
+            "1.0.1",
+        ],
+    );
+    assert_match_none(r, &["0.9.9", "1.0.1-0"]);
+
+    let ref r = req(">1.0.0-0");
+    assert_match_all(r, &["1.0.0-0.0", "1.0.0-1", "1.0.0-alpha", "1.0.0"]);
+    assert_match_none(r, &["1.0.0-0"]);
+
+    let ref r = req("<1.0.0-0");
+    assert_match_all(r, &["0.9.9"]);
+    assert_match_none(r, &["1.0.0-0", "1.0.0-alpha", "1.0.0", "0.9.9-0"]);
+
+    let ref r = req("<=1.0.0-0");
+    assert_match_all(r, &["0.9.9", "1.0.0-0"]);
+    assert_match_none(r, &["1.0.0-0.0", "1.0.0-1", "1.0.0"]);
+
+    let ref r = req(">=0.0.0-0");
+    assert_match_all(r, &["0.0.0-0", "0.0.0-alpha", "0.0.0", "1.0.0"]);
+    assert_match_none(r, &["1.0.0-0"]);
+
+    let ref r = req("=1.0.0-0");
+    assert_match_all(r, &["1.0.0-0"]);
+    assert_match_none(r, &["1.0.0-0.0", "1.0.0"]);
+}
+
+#[test]
+fn test_sample_min_max() {
+    fn samples(text: &str) -> (Option<String>, Option<String>) {
+        let r = semver::VersionReq::parse(text).unwrap();
+        let min = r.sample_min();
+        let max = r.sample_max();
+        for sample in min.iter().chain(max.iter()) {
+            assert!(r.matches(sample), "{} does not match {}", text, sample);
+        }
+        (
+            min.as_ref().map(ToString::to_string),
+            max.as_ref().map(ToString::to_string),
+        )
+    }
+    let some = |min: &str, max: &str| (Some(min.to_owned()), Some(max.to_owned()));
+    let max = u64::MAX;
+
+    assert_eq!(
+        samples("^1.2.3"),
+        some("1.2.3", &format!("1.{}.{}", max, max)),
+    );
+    assert_eq!(
+        samples(">=1 <2"),
+        some("1.0.0", &format!("1.{}.{}", max, max)),
+    );
+    assert_eq!(samples("~1.2.3"), some("1.2.3", &format!("1.2.{}", max)));
+    assert_eq!(samples(">1.2.3 <=1.4.0"), some("1.2.4", "1.4.0"));
+    assert_eq!(
+        samples(">1.2.3-rc.1 <1.2.3"),
+        some("1.2.3-rc.1.0", "1.2.3-rc.1.0")
+    );
+    assert_eq!(samples("<1.2.3-rc.1"), some("0.0.0", "1.2.2"));
+    assert_eq!(samples("<0.0.0-1"), some("0.0.0-0", "0.0.0-0"));
+    assert_eq!(samples("=1.2.3"), some("1.2.3", "1.2.3"));
+    assert_eq!(samples("1.2.3 || 2.0.0"), some("1.2.3", "2.0.0"));
+    assert_eq!(
+        samples("<1 || >=3"),
+        some("0.0.0", &format!("{0}.{0}.{0}", max))
+    );
+    assert_eq!(samples("*"), some("0.0.0", &format!("{0}.{0}.{0}", max)));
+    assert_eq!(samples("<0.0.0"), (None, None));
+    assert_eq!(samples(">=2 <1"), (None, None));
+
+    // A lower bound that is a pre-release which the requirement does not
+    // match, with releases or other pre-releases above it that it does.
+    let min = |text: &str| samples(text).0.unwrap();
+    assert_eq!(min(">=1.1.1-alpha <=1.1"), "1.1.1");
+    assert_eq!(min("1 >=1.1.1-alpha"), "1.1.1");
+    assert_eq!(min(">=0 >=0.2.1-rc.1"), "0.2.1");
+    assert_eq!(min(">1.2.2 <1.2.3-rc.1"), "1.2.3-0");
+    assert_eq!(min(">=1.1.1-alpha <1.1.1 || >=1.1.1 <1.2"), "1.1.1-alpha");
+    assert_eq!(min("<=2.1.2-rc.1 >=2.1.2-rc.1 || ^0.1.1"), "0.1.1");
+
+    // Pre-releases matched by a partial comparator's major.minor.patch.
+    assert_eq!(min("=0.0.0-rc.1 ^0.0 || ^2.1.0"), "0.0.0-rc.1");
+    assert_eq!(min(">0.0.0-rc.1 ^0.0"), "0.0.0-rc.1.0");
+}
+
+#[test]
+fn test_sample_min_random() {
+    let comparators = small_comparators();
+    let versions = small_versions();
+
+    let mut random = Xorshift::new();
+    let mut comparator = || random.choose(&comparators).as_str();
+    for _ in 0..2000 {
+        let text = format!("{} {} || {}", comparator(), comparator(), comparator());
+        let r = semver::VersionReq::parse(&text).unwrap();
+        let matched = versions.iter().filter(|v| r.matches(v)).min();
+        match r.sample_min() {
+            Some(min) => {
+                assert!(r.matches(&min), "{} does not match {}", text, min);
+                if let Some(matched) = matched {
+                    assert!(min <= *matched, "{}: {} > {}", text, min, matched);
+                }
+            }
+            None => assert_eq!(matched, None, "{}", text),
+        }
+    }
+}
+
+#[test]
+fn test_version_req_parser() {
+    fn parse_chunks(chunks: &[&str]) -> Result<String, String> {
+        let mut parser = VersionReqParser::new();
+        for chunk in chunks {
+            parser.feed(chunk).map_err(|err| err.to_string())?;
+        }
+        match parser.finish() {
+            Ok(req) => Ok(format!("{:?}", req)),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    let texts = [
+        "",
+        "  ",
+        "latest",
+        "*",
+        "* || 1",
+        "x, 1",
+        ">=0",
+        ">=0 ||",
+        "^1.2.3",
+        ">=1.0.0 <2.0.0",
+        "1.2.3 - 2.3.4 || ^3 || 4.x",
+        "~1 ||  <0.5.0-rc.1 || =1.2.3",
+        "||",
+        " || 1",
+        "1 || || 2",
+        "1 ||| 2",
+        "1 | 2",
+        "^1.q || 2",
+        "2 || ^1.q",
+        "1.0.0-é || 2",
+    ];
+
+    for text in &texts {
+        let expected = match semver::VersionReq::parse(text) {
+            Ok(req) => Ok(format!("{:?}", req)),
+            Err(err) => Err(err.to_string()),
+        };
+        assert_eq!(parse_chunks(&[text]), expected, "{:?}", text);
+
+        let chars: Vec<String> = text.chars().map(String::from).collect();
+        let chars: Vec<&str> = chars.iter().map(String::as_str).collect();
+        assert_eq!(parse_chunks(&chars), expected, "{:?}", text);
+
+        for (i, _) in text.char_indices() {
+            let (left, right) = text.split_at(i);
+            assert_eq!(parse_chunks(&[left, right]), expected, "{:?}", text);
+            assert_eq!(parse_chunks(&[left, "", right]), expected, "{:?}", text);
+        }
+    }
+
+    // An error in a complete alternative is reported without waiting for the
+    // rest of the text, and again by every later call.
+    let mut parser = VersionReqParser::new();
+    parser.feed("^1.q |").unwrap();
+    let err = parser.feed("| 2").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected character 'q' while parsing minor version number",
+    );
+    let err = parser.feed("1").unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected character 'q' while parsing minor version number",
+    );
+    let err = parser.finish().unwrap_err();
+    assert_to_string(
+        err,
+        "unexpected character 'q' while parsing minor version number",
+    );
+}
+
+#[test]
+fn test_matches_with() {
+    fn modes(text: &str, v: &str) -> (bool, bool) {
+        let r = semver::VersionReq::parse(text).unwrap();
+        let v = version(v);
+        assert_eq!(
+            r.matches_with(&v, IncludeBoundsMode::default()),
+            r.matches(&v),
+        );
+        (
+            r.matches_with(&v, IncludeBoundsMode::Exclusive),
+            r.matches_with(&v, IncludeBoundsMode::Inclusive),
+        )
+    }
+
+    assert_eq!(modes("~1.2.3", "1.3.0"), (false, true));
+    assert_eq!(modes("~1.2.3", "1.3.0+build"), (false, true));
+    assert_eq!(modes("~1.2.3", "1.2.9"), (true, true));
+    assert_eq!(modes("~1.2.3", "1.3.1"), (false, false));
+    assert_eq!(modes("~1.2.3", "1.3.0-rc.1"), (false, false));
+    assert_eq!(modes("~1.2.3", "1.2.2"), (false, false));
+
+    assert_eq!(modes("^1.2.3", "2.0.0"), (false, true));
+    assert_eq!(modes("<2.0.0", "2.0.0"), (false, true));
+    assert_eq!(modes("<2.0.0-rc.1", "2.0.0-rc.1"), (false, true));
+    assert_eq!(modes("=1.2", "1.3.0"), (false, true));
+    assert_eq!(modes(">=1.0.0 <1.3.0", "1.3.0"), (false, true));
+    assert_eq!(modes("<=1.2.3", "1.2.4"), (false, false));
+    assert_eq!(modes("1.0.0 - 1.2", "1.3.0"), (false, true));
+    assert_eq!(modes("1.0.0 - 1.2.3", "1.2.4"), (false, false));
+    assert_eq!(modes("<1.0.0 || ~2.1", "2.2.0"), (false, true));
+
+    // Only upper bounds are affected.
+    assert_eq!(modes(">1.2.3", "1.2.3"), (false, false));
+}
+
+#[test]
+fn test_intersect_all() {
+    let all = |texts: &[&str]| {
+        let reqs = texts
+            .iter()
+            .map(|text| semver::VersionReq::parse(text).unwrap());
+        req(&semver::VersionReq::intersect_all(reqs).to_string())
+    };
+
+    let ref r = all(&["^1.2 || ^2", "<2.5"]);
+    assert_to_string(r, "^1.2 <2.5 || ^2 <2.5");
+    assert_match_all(r, &["1.2.0", "1.9.9", "2.0.0", "2.4.9"]);
+    assert_match_none(r, &["1.1.9", "2.5.0", "3.0.0"]);
+
+    let ref r = all(&["1.0.0 - 1.4", "*", ">1.2.0"]);
+    assert_to_string(r, ">=1.0.0 <=1.4 >1.2.0");
+    assert_match_all(r, &["1.2.1", "1.4.9"]);
+    assert_match_none(r, &["1.2.0", "1.5.0"]);
+
+    let ref r = all(&[">=1.0.0-rc.1", "<2"]);
+    assert_match_all(r, &["1.0.0-rc.2", "1.5.0"]);
+
+    let ref r = all(&["^1", "^2"]);
+    assert_match_none(r, &["1.0.0", "2.0.0"]);
+
+    assert_eq!(
+        semver::VersionReq::intersect_all(Vec::new()),
+        semver::VersionReq::STAR
+    );
+}
+
+#[test]
+fn test_pre_on_partial() {
+    let err = req_err(">=1-rc");
+    assert_to_string(
+        err,
+        "pre-release must follow a patch version number, not a major version number",
+    );
+
+    let err = req_err("1.2-beta.1");
+    assert_to_string(
+        err,
+        "pre-release must follow a patch version number, not a minor version number",
+    );
+
+    let err = req_err("^1.2.3 || ~2-alpha");
+    assert_to_string(
+        err,
+        "pre-release must follow a patch version number, not a major version number",
+    );
+
+    let err = Comparator::parse("<1-rc").unwrap_err();
+    assert_to_string(
+        err,
+        "pre-release must follow a patch version number, not a major version number",
+    );
+
+    // With a digit after the hyphen, it is a hyphen range.
+    let ref r = req("1-2");
+    assert_match_all(r, &["1.0.0", "2.9.9"]);
+    let ref r = req(">=1.0.0-rc");
+    assert_match_all(r, &["1.0.0-rc", "1.0.0"]);
+}
+
+#[test]
+fn test_comparator_intersects() {
+    let intersects = |a: &str, b: &str| {
+        let a = Comparator::parse(a).unwrap();
+        let b = Comparator::parse(b).unwrap();
+        assert_eq!(a.intersects(&b), b.intersects(&a));
+        a.intersects(&b)
+    };
+
+    // Disjoint.
+    assert!(!intersects("<1.0.0", ">=2.0.0"));
+    assert!(!intersects("^1.2", "^2"));
+    assert!(!intersects("=1.2.3", "=1.2.4"));
+    assert!(!intersects("~1.2", ">=1.3"));
+
+    // Touching.
+    assert!(!intersects("<2.0.0", ">=2.0.0"));
+    assert!(!intersects("<=2.0.0", ">2.0.0"));
+    assert!(!intersects("^1", "2.x"));
+    assert!(intersects("<=2.0.0", ">=2.0.0"));
+    assert!(intersects("<=2", ">=2"));
+    assert!(intersects("=2.0.0", ">=2.0.0"));
+
+    // Overlapping.
+    assert!(intersects("^1.2", "<1.5"));
+    assert!(intersects(">1.2.3", "<1.2.5"));
+    assert!(intersects("~1.2.3", "1.2.x"));
+    assert!(intersects(">=0", "=0.0.0"));
+    assert!(intersects("=1.2.3-rc.1", ">=1.2.3-alpha"));
+
+    // Only pre-releases in between.
+    assert!(!intersects(">1.2.3-rc.1", "<1.2.3"));
+    assert!(!intersects(">1.2.3", "<1.2.4-rc.1"));
+    assert!(intersects(">1.2.3-rc.1", "<1.2.3-rc.2"));
+}
+
+#[test]
+fn test_pinned_to() {
+    let pin = |r: &str, v: &str| req(r).pinned_to(&version(v)).map(|pin| pin.to_string());
+
+    // Matching.
+    assert_eq!(pin("^1.2", "1.4.0"), Some("=1.4.0".to_owned()));
+    assert_eq!(pin("*", "0.0.0"), Some("=0.0.0".to_owned()));
+    assert_eq!(
+        pin(">=1.2.3-rc.1", "1.2.3-rc.2"),
+        Some("=1.2.3-rc.2".to_owned())
+    );
+    assert_eq!(
+        pin("~1.2 || ^3", "3.1.0+build.5"),
+        Some("=3.1.0".to_owned())
+    );
+
+    // Not matching.
+    assert_eq!(pin("^1.2", "2.0.0"), None);
+    assert_eq!(pin("^1.2", "1.1.9"), None);
+    assert_eq!(pin("^1.2", "1.5.0-rc.1"), None);
+    assert_eq!(pin("<0.0.0", "0.0.0"), None);
+
+    // The pin stays within the original requirement.
+    let ref r = req("~1.2 || ^3");
+    let ref v = version("1.2.7");
+    assert!(r.pinned_to(v).unwrap().matches(v));
+}
+
+#[test]
+fn test_count_matching() {
+    let versions = [
+        version("0.9.0"),
+        version("1.0.0-rc.1"),
+        version("1.0.0"),
+        version("1.2.0-beta"),
+        version("1.2.0"),
+        version("1.5.3+build"),
+        version("2.0.0-alpha"),
+        version("2.0.0"),
+    ];
+
+    assert_eq!(req("^1").count_matching(&versions), 3);
+    assert_eq!(req("*").count_matching(&versions), 5);
+    assert_eq!(req(">=1.0.0-rc.1").count_matching(&versions), 5);
+    assert_eq!(req("^1.2.0-beta").count_matching(&versions), 3);
+    assert_eq!(
+        req(">=1.0.0-rc.1 || >=2.0.0-alpha").count_matching(&versions),
+        6
+    );
+    assert_eq!(req("<0.0.0").count_matching(&versions), 0);
+    assert_eq!(req("^1").count_matching(&[]), 0);
+}
+
+#[test]
+fn test_simplify() {
+    let simplify = |text: &str| {
+        semver::VersionReq::parse(text)
+            .unwrap()
+            .simplify()
+            .to_string()
+    };
+
+    assert_eq!(simplify(">=1.2.3 <=1.2.3"), "=1.2.3");
+    assert_eq!(simplify("<=1.2.3 >=1.2.3"), "=1.2.3");
+    assert_eq!(simplify("1.2.3 - 1.2.3"), "=1.2.3");
+    assert_eq!(simplify(">=1.2.3-rc.1 <=1.2.3-rc.1"), "=1.2.3-rc.1");
+    assert_eq!(simplify(">=1.2 <=1.2"), "=1.2");
+    assert_eq!(simplify(">=1 <=1.2.3 >=1.2.3"), ">=1 =1.2.3");
+    assert_eq!(simplify(">=1.2.3 <=1.2.3 || ^2"), "=1.2.3 || ^2");
+
+    // Unchanged.
+    assert_eq!(simplify(">=1.2.3 <1.2.3"), ">=1.2.3 <1.2.3");
+    assert_eq!(simplify(">=1.2.3 <=1.2.4"), ">=1.2.3 <=1.2.4");
+    assert_eq!(simplify(">=1.2.3 <=1.2.3-rc.1"), ">=1.2.3 <=1.2.3-rc.1");
+    assert_eq!(simplify(">=1.2 <=1.2.0"), ">=1.2 <=1.2.0");
+    assert_eq!(simplify("1.2.3 - 1.2.4"), "=1.2.3 - =1.2.4");
+    assert_eq!(simplify("^1.2.3"), "^1.2.3");
+    assert_eq!(simplify("*"), "*");
+
+    // Still matches only 1.2.3.
+    let ref r = req(&simplify(">=1.2.3 <=1.2.3"));
+    assert_match_all(r, &["1.2.3", "1.2.3+build"]);
+    assert_match_none(r, &["1.2.2", "1.2.4", "1.2.3-rc.1", "1.3.0"]);
+
+    let joined = semver::VersionReq::intersect_all(vec![
+        semver::VersionReq::parse(">=1.2.3").unwrap(),
+        semver::VersionReq::parse("<=1.2.3").unwrap(),
+    ]);
+    assert_eq!(joined.simplify().to_string(), "=1.2.3");
+}
+
+#[test]
+fn test_from_std_range() {
+    let versions: Vec<semver::Version> = [
+        "0.9.9", "1.0.0", "1.0.1", "1.2.3", "1.9.9", "2.0.0", "2.0.1", "3.0.0",
+    ]
+    .iter()
+    .map(|text| version(text))
+    .collect();
+
+    for &(start, end) in &[("1.0.0", "2.0.0"), ("1.2.3", "1.2.3"), ("0.0.0", "1.0.1")] {
+        let (start, end) = (version(start), version(end));
+
+        let inclusive = semver::VersionReq::from(start.clone()..=end.clone());
+        for v in &versions {
+            let contains = start <= *v && *v <= end;
+            assert_eq!(inclusive.matches(v), contains, "{} {}", inclusive, v);
+        }
+
+        let exclusive = semver::VersionReq::from(start.clone()..end.clone());
+        for v in &versions {
+            let contains = start <= *v && *v < end;
+            assert_eq!(exclusive.matches(v), contains, "{} {}", exclusive, v);
+        }
+    }
+
+    let from = |range: Range<semver::Version>| semver::VersionReq::from(range).to_string();
+    let from_inclusive =
+        |range: RangeInclusive<semver::Version>| semver::VersionReq::from(range).to_string();
+
+    assert_eq!(from(version("1.0.0")..version("2.0.0")), ">=1.0.0 <2.0.0");
+    assert_eq!(
+        from_inclusive(version("1.0.0")..=version("2.0.0")),
+        "=1.0.0 - =2.0.0",
+    );
+
+    // Pre-releases of the endpoints are matched, others are not.
+    let ref r = req(&from(version("1.0.0-rc.1")..version("2.0.0")));
+    assert_match_all(r, &["1.0.0-rc.1", "1.0.0-rc.2", "1.0.0", "1.5.0"]);
+    assert_match_none(r, &["1.0.0-beta", "1.5.0-rc.1", "2.0.0-rc.1", "2.0.0"]);
+
+    // Empty ranges match nothing.
+    assert_eq!(from(version("1.2.3")..version("1.2.3")), "<0.0.0");
+    assert_eq!(from(version("2.0.0")..version("1.0.0")), "<0.0.0");
+    assert_eq!(
+        from_inclusive(version("2.0.0")..=version("1.0.0")),
+        "<0.0.0"
+    );
+}
+
+#[test]
+fn test_explain_mismatch() {
+    let explain = |r: &str, v: &str| {
+        semver::VersionReq::parse(r)
+            .unwrap()
+            .explain_mismatch(&version(v))
+    };
+
+    // Matching.
+    assert_eq!(explain(">=2.0.0", "2.0.0"), None);
+    assert_eq!(explain("^1.2.3-rc.1", "1.2.3-rc.2"), None);
+    assert_eq!(explain("*", "1.2.3"), None);
+
+    // Numeric miss.
+    assert_eq!(
+        explain(">=2.0.0", "1.5.0").unwrap(),
+        "1.5.0 is not within >=2.0.0",
+    );
+    assert_eq!(
+        explain("^1.2 || ~3.1", "2.0.0+build").unwrap(),
+        "2.0.0+build is not within ^1.2 || ~3.1",
+    );
+    assert_eq!(
+        explain("<1.2.3-rc.1", "1.2.3-rc.2").unwrap(),
+        "1.2.3-rc.2 is not within <1.2.3-rc.1",
+    );
+
+    // Pre-release miss.
+    assert_eq!(
+        explain(">=1.0.0", "2.0.0-rc.1").unwrap(),
+        "2.0.0-rc.1 is a pre-release, and no comparator in >=1.0.0 allows pre-releases of 2.0.0",
+    );
+    assert_eq!(
+        explain("*", "1.0.0-alpha").unwrap(),
+        "1.0.0-alpha is a pre-release, and no comparator in * allows pre-releases of 1.0.0",
+    );
+    assert_eq!(
+        explain("^1.2.3-rc.1", "1.3.0-beta").unwrap(),
+        "1.3.0-beta is a pre-release, and no comparator in ^1.2.3-rc.1 allows pre-releases of 1.3.0",
+    );
+}
+
+#[test]
+fn test_dedup() {
+    let r = semver::VersionReq::parse("^1.2 || >=3 <4 || 5.0.0 - 6").unwrap();
+    let mut union = semver::VersionReq::union_all(vec![r.clone(), r.clone()]);
+    assert_eq!(union.ranges.len(), 6);
+    union.dedup();
+    assert_eq!(union, r);
+
+    // First occurrences stay in order.
+    let mut r = semver::VersionReq::parse("^2 || ^1 || ^2 || ~3 || ^1").unwrap();
+    r.dedup();
+    assert_to_string(&r, "^2 || ^1 || ~3");
+
+    // Only structurally equal alternatives are removed.
+    let mut r = semver::VersionReq::parse("^1 || >=1.0.0 <2.0.0 || ^1.0").unwrap();
+    r.dedup();
+    assert_to_string(&r, "^1 || >=1.0.0 <2.0.0 || ^1.0");
+
+    let mut r = semver::VersionReq::STAR;
+    r.dedup();
+    assert_eq!(r, semver::VersionReq::STAR);
+}
+
+#[test]
+fn test_caret_zero_zero() {
+    // With a zero major and minor version, a caret pins the patch version.
+    for patch in 0..4 {
+        let ref r = req(&format!("^0.0.{}", patch));
+        for other in 0..4 {
+            let v = format!("0.0.{}", other);
+            assert_eq!(r.matches(&version(&v)), other == patch, "{} {}", r, v);
+        }
+        assert_match_none(
+            r,
+            &[
+                &format!("0.1.{}", patch),
+                &format!("1.0.{}", patch),
+                &format!("0.0.{}-rc.1", patch),
+                &format!("0.0.{}+build", patch + 1),
+            ],
+        );
+        assert_match_all(r, &[&format!("0.0.{}+build", patch)]);
+    }
+
+    let ref r = req("^0.0.0");
+    assert_match_all(r, &["0.0.0"]);
+    assert_match_none(
+        r,
+        &["0.0.1", "0.0.0-0", "0.1.0", "18446744073709551615.0.0"],
+    );
+
+    let ref r = req("^0.0.1-rc.1");
+    assert_match_all(r, &["0.0.1-rc.1", "0.0.1-rc.2", "0.0.1"]);
+    assert_match_none(r, &["0.0.1-alpha", "0.0.0", "0.0.2", "0.0.2-rc.1"]);
+
+    let ref r = req("^0.0.18446744073709551615");
+    assert_match_all(r, &["0.0.18446744073709551615"]);
+    assert_match_none(r, &["0.0.18446744073709551614", "0.1.0"]);
+}
+
+#[test]
+fn test_is_any_is_wildcard() {
+    let classify = |text: &str| {
+        let r = semver::VersionReq::parse(text).unwrap();
+        (r.is_any(), r.is_wildcard())
+    };
+
+    for text in &["*", "x", "X", "", ">=0", " * "] {
+        assert_eq!(classify(text), (true, true), "{}", text);
+    }
+    for text in &["1.x", "1.2.*", "1.x || 2.*", "1.x 1.2.x"] {
+        assert_eq!(classify(text), (false, true), "{}", text);
+    }
+    for text in &[
+        "^1",
+        "1.x || ^2",
+        "1.x - 2.x",
+        ">=0 || ^1",
+        ">=1.x",
+        "=1.2.3",
+    ] {
+        assert_eq!(classify(text), (false, false), "{}", text);
+    }
+
+    assert!(semver::VersionReq::STAR.is_any());
+    assert!(semver::VersionReq::default().is_any());
+}
+
+#[test]
+fn test_complexity() {
+    let complexity = |text: &str| semver::VersionReq::parse(text).unwrap().complexity();
+
+    assert_eq!(complexity("*"), 0);
+    assert_eq!(complexity("^1"), 1);
+    assert_eq!(complexity("1.2.3 - 2"), 2);
+    assert_eq!(complexity(">=1 <2 ~1.5"), 3);
+
+    let chain = (0..100)
+        .map(|major| format!("^{}", major))
+        .collect::<Vec<_>>()
+        .join(" || ");
+    assert_eq!(complexity(&chain), 100);
+    assert!(complexity(&chain) > complexity("^1") * 50);
+}
+
+#[test]
+fn test_matches_with_reason() {
+    let reason = |req: &str, ver: &str| {
+        let r = semver::VersionReq::parse(req).unwrap();
+        let v = semver::Version::parse(ver).unwrap();
+        let reason = r.matches_with_reason(&v);
+        assert_eq!(
+            reason == MatchReason::Matched,
+            r.matches(&v),
+            "{} {}",
+            req,
+            ver
+        );
+        reason
+    };
+
+    assert_eq!(reason("^1.2", "1.5.0"), MatchReason::Matched);
+    assert_eq!(reason("*", "0.0.0"), MatchReason::Matched);
+    assert_eq!(reason(">=1.0.0-rc.1", "1.0.0-rc.2"), MatchReason::Matched);
+
+    assert_eq!(reason("^1.2", "2.0.0"), MatchReason::NoRangeMatched);
+    assert_eq!(reason("^1.2", "1.1.0-rc.1"), MatchReason::NoRangeMatched);
+    assert_eq!(
+        reason("=1.0.0-rc.1", "1.0.0-rc.2"),
+        MatchReason::NoRangeMatched
+    );
+    assert_eq!(reason("<0.0.0", "0.0.0"), MatchReason::NoRangeMatched);
+
+    assert_eq!(
+        reason("^1.2", "1.5.0-rc.1"),
+        MatchReason::PrereleaseRejected
+    );
+    assert_eq!(reason("*", "1.0.0-rc.1"), MatchReason::PrereleaseRejected);
+    assert_eq!(
+        reason(">=1.0.0-rc.1", "1.1.0-rc.1"),
+        MatchReason::PrereleaseRejected
+    );
+    assert_eq!(
+        reason("^1 || =2.0.0-rc.1", "1.5.0-rc.1"),
+        MatchReason::PrereleaseRejected
+    );
+}
+
+#[test]
+fn test_comparator_display() {
+    let cmp = |op: Op, major: u64, minor: Option<u64>, patch: Option<u64>| Comparator {
+        op,
+        major,
+        minor,
+        patch,
+        pre: semver::Prerelease::EMPTY,
+    };
+
+    assert_to_string(cmp(Op::GreaterEq, 1, None, None), ">=1");
+    assert_to_string(cmp(Op::Tilde, 1, Some(2), None), "~1.2");
+    assert_to_string(cmp(Op::Caret, 1, Some(2), Some(3)), "^1.2.3");
+    assert_to_string(cmp(Op::Caret, 1, None, None), "^1");
+    assert_to_string(cmp(Op::Exact, 0, Some(0), None), "=0.0");
+    assert_to_string(cmp(Op::Wildcard, 1, None, None), "1.*");
+    assert_to_string(cmp(Op::Wildcard, 1, Some(2), None), "1.2.*");
+
+    let mut with_pre = cmp(Op::Less, 1, Some(2), Some(3));
+    with_pre.pre = semver::Prerelease::new("rc.1").unwrap();
+    assert_to_string(&with_pre, "<1.2.3-rc.1");
+
+    // Parsing and displaying keeps only the components that were written.
+    for text in &["^1", "~1.2", ">=1.2.3", "<1.2.3-rc.1", "=1", "1.*", "~>1.2"] {
+        let cmp = Comparator::parse(text).unwrap();
+        assert_to_string(&cmp, text);
+        let r = semver::VersionReq::parse(text).unwrap();
+        assert_to_string(&r, text);
+    }
+}
+
+#[test]
+fn test_into_comparators() {
+    let into = |text: &str| {
+        let r = semver::VersionReq::parse(text).unwrap();
+        r.into_comparators()
+            .iter()
+            .map(|cmp| cmp.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(into("1.2.3 - 2.3.4"), [">=1.2.3", "<=2.3.4"]);
+    assert_eq!(into("1.2 - 2"), [">=1.2", "<=2"]);
+    assert_eq!(into("1.* - 2.x"), [">=1", "<=2"]);
+    assert_eq!(into("1.2.3-rc.1 - 2"), [">=1.2.3-rc.1", "<=2"]);
+    assert_eq!(into("^1.2"), ["^1.2"]);
+    assert_eq!(
+        into(">=1 <2 || ~3 || 4 - 5"),
+        [">=1", "<2", "~3", ">=4", "<=5"]
+    );
+    assert!(into("*").is_empty());
+
+    let cmps = semver::VersionReq::parse("1.2.3 - 2.3.4")
+        .unwrap()
+        .into_comparators();
+    assert_eq!(cmps[0].op, Op::GreaterEq);
+    assert_eq!(cmps[1].op, Op::LessEq);
+    assert_eq!(cmps[0], Comparator::parse(">=1.2.3").unwrap());
+    assert_eq!(cmps[1], Comparator::parse("<=2.3.4").unwrap());
+
+    // The two comparators match the same as the hyphen range.
+    let ref r = req(">=1.2.3 <=2.3.4");
+    assert_match_all(r, &["1.2.3", "2.0.0", "2.3.4"]);
+    assert_match_none(r, &["1.2.2", "2.3.5"]);
+}
+
+#[test]
+fn test_matches_with_pre_allowlist() {
+    let matches = |req: &str, ver: &str, allowed: &[&str]| {
+        let r = semver::VersionReq::parse(req).unwrap();
+        let v = semver::Version::parse(ver).unwrap();
+        r.matches_with_pre_allowlist(&v, allowed)
+    };
+
+    // The same numeric version, on an allowed and a rejected channel.
+    assert!(matches("^1.2", "1.5.0-rc.1", &["rc"]));
+    assert!(matches("^1.2", "1.5.0-rc", &["rc"]));
+    assert!(!matches("^1.2", "1.5.0-alpha.1", &["rc"]));
+    assert!(matches("^1.2", "1.5.0-alpha.1", &["rc", "alpha"]));
+    assert!(!matches("^1.2", "1.5.0-rc.1", &[]));
+
+    // The whole first identifier must match.
+    assert!(!matches("^1.2", "1.5.0-rc1", &["rc"]));
+    assert!(!matches("^1.2", "1.5.0-RC.1", &["rc"]));
+    assert!(!matches("^1.2", "1.5.0-beta.rc", &["rc"]));
+
+    // Still only within the ranges, by precedence: `1.5.0-rc.1` is below
+    // `1.5.0`, and a wider req never matches less.
+    for req in &["^1", ">=1.4 <1.6", ">=1.5.0-rc.1"] {
+        assert!(matches(req, "1.5.0-rc.1", &["rc"]), "{}", req);
+        assert!(!matches(req, "1.5.0-alpha.1", &["rc"]), "{}", req);
+    }
+    for req in &[">=1.5.0", ">=1.5.0-rc.2", "=1.5.0", "<1.5.0-rc.1", "1.4"] {
+        assert!(!matches(req, "1.5.0-rc.1", &["rc"]), "{}", req);
+    }
+    assert!(!matches("^1.2", "2.0.0-rc.1", &["rc"]));
+    assert!(!matches("^1.2.0", "1.2.0-rc.1", &["rc"]));
+    assert!(!matches("^1.2", "1.1.0", &["rc"]));
+
+    // Otherwise the same as `matches`.
+    assert!(matches("^1.2", "1.5.0", &["rc"]));
+    assert!(matches(">=1.5.0-alpha.1", "1.5.0-alpha.2", &["rc"]));
+    assert!(!matches("*", "1.0.0-beta", &["rc"]));
+    assert!(matches("*", "1.0.0-rc.2", &["rc"]));
+}
+
+#[test]
+fn test_comparator_eq_version() {
+    let eq = |comparator: &str, ver: &str| {
+        let c = Comparator::parse(comparator).unwrap();
+        let v = semver::Version::parse(ver).unwrap();
+        assert_eq!(c == v, v == c);
+        c == v
+    };
+
+    assert!(eq("=1.2.3", "1.2.3"));
+    assert!(eq("=1.2.3", "1.2.3+build.5"));
+    assert!(eq("=1.2.3-rc.1", "1.2.3-rc.1"));
+
+    assert!(!eq("=1.2.3", "1.2.4"));
+    assert!(!eq("=1.2.3", "1.2.3-rc.1"));
+    assert!(!eq("=1.2.3-rc.1", "1.2.3"));
+    assert!(!eq("^1.2.3", "1.2.3"));
+    assert!(!eq(">=1.2.3", "1.2.3"));
+    assert!(!eq("=1.2", "1.2.3"));
+    assert!(!eq("1.2.*", "1.2.3"));
+    assert!(!eq("1.2.*", "1.2.0"));
+    assert!(!eq("1.*", "1.0.0"));
+
+    let pin = Comparator::parse("=1.2.3").unwrap();
+    let versions = ["1.2.2", "1.2.3", "1.2.3+meta", "1.3.0"]
+        .iter()
+        .map(|v| semver::Version::parse(v).unwrap())
+        .collect::<Vec<_>>();
+    let pinned = versions.iter().filter(|v| pin == **v).count();
+    assert_eq!(pinned, 2);
+}
+
+#[test]
+fn test_comma_is_not_a_separator() {
+    // Comparators are separated by whitespace, so a comma is never accepted,
+    // whether between, after, or doubled between comparators.
+    for text in &[
+        ">=1.0, <2.0",
+        ">=1.0 <2.0,",
+        ">=1.0, <2.0,",
+        ">=1.0,,<2.0",
+        "1.0,",
+    ] {
+        let err = req_err(text);
+        assert_to_string(
+            err,
+            "unexpected character ',' while parsing major version number",
+        );
+    }
+
+    let ref r = req(">=1.0 <2.0 ");
+    assert_to_string(r, ">=1.0 <2.0");
+}
+
+#[test]
+fn test_permissiveness() {
+    let cmp = |a: &str, b: &str| {
+        let a = semver::VersionReq::parse(a).unwrap();
+        let b = semver::VersionReq::parse(b).unwrap();
+        a.permissiveness(&b)
+    };
+
+    // Subset and superset.
+    assert_eq!(cmp("^1.4", "^1.2"), Some(Ordering::Less));
+    assert_eq!(cmp("^1.2", "^1.4"), Some(Ordering::Greater));
+    assert_eq!(cmp("=1.2.3", "~1.2"), Some(Ordering::Less));
+    assert_eq!(cmp("^1 || ^3", "^1"), Some(Ordering::Greater));
+    assert_eq!(cmp("<0.0.0", "^1"), Some(Ordering::Less));
+    assert_eq!(cmp("<0.0.0", "=0.0.0-rc.1"), Some(Ordering::Less));
+    assert_eq!(cmp("<0.0.0", "^1 <1.0.0"), Some(Ordering::Equal));
+    assert_eq!(cmp("*", ">=1.0.0"), Some(Ordering::Greater));
+    assert_eq!(cmp("<=1.2.3", "<1.2.3"), Some(Ordering::Greater));
+
+    // The same versions, however written.
+    assert_eq!(cmp("^1.2", "^1.2"), Some(Ordering::Equal));
+    assert_eq!(cmp("^1.2", ">=1.2.0 <2.0.0"), Some(Ordering::Equal));
+    assert_eq!(cmp("1.2.3 - 1.4", ">=1.2.3 <1.5.0"), Some(Ordering::Equal));
+    assert_eq!(cmp("^1 || ^2", ">=1 <3"), Some(Ordering::Equal));
+    assert_eq!(cmp("*", ">=0.0.0"), Some(Ordering::Equal));
+
+    // Only the pre-releases that the pre-release rule lets match count, even
+    // where the ranges cover others.
+    assert_eq!(cmp("^1", ">=1.0.0-0 <2.0.0-0"), Some(Ordering::Less));
+    assert_eq!(cmp(">=1.0.0-0 <2.0.0-0", "^1"), Some(Ordering::Greater));
+    assert_eq!(cmp("<0.0.1", "<0.0.1-0"), Some(Ordering::Equal));
+    assert_eq!(cmp("<0.0.0-0", "<0.0.0"), Some(Ordering::Equal));
+    assert_eq!(cmp("<1.2.3-rc.1", "<1.2.3"), Some(Ordering::Greater));
+    assert_eq!(cmp("^1.2.3-rc.1", "^1.2.3-beta"), Some(Ordering::Less));
+    assert_eq!(cmp("^1.2.3-rc.1", "^1.2.3"), Some(Ordering::Greater));
+    assert_eq!(cmp(">=1.2.3-rc.1 <2", ">=1.2.2 <2"), None);
+    assert_eq!(cmp("*", ">=1.0.0-rc.1"), None);
+
+    // Neither contains the other.
+    assert_eq!(cmp("^1", "^2"), None);
+    assert_eq!(cmp(">=1.2 <1.8", "^1.5"), None);
+    assert_eq!(cmp("^1 || ^3", "^1 || ^2"), None);
+}
+
+#[test]
+fn test_permissiveness_random() {
+    // Partial versions are left out: a comparator such as `>1` matches a
+    // pre-release by its major.minor.patch, which is not what its interval of
+    // precedence covers, so permissiveness is not exact for them.
+    let comparators = small_comparators()
+        .into_iter()
+        .filter(|text| text.matches('.').count() == 2 && !text.ends_with('*'))
+        .collect::<Vec<_>>();
+    let versions = small_versions();
+
+    let mut random = Xorshift::new();
+    let mut comparator = || random.choose(&comparators).as_str();
+    for _ in 0..2000 {
+        let a = format!("{} {}", comparator(), comparator());
+        let b = format!("{} || {}", comparator(), comparator());
+        let ra = semver::VersionReq::parse(&a).unwrap();
+        let rb = semver::VersionReq::parse(&b).unwrap();
+        let ordering = ra.permissiveness(&rb);
+        for v in &versions {
+            match ordering {
+                Some(Ordering::Less) | Some(Ordering::Equal) if ra.matches(v) => {
+                    assert!(rb.matches(v), "{} vs {}: {:?} {}", a, b, ordering, v);
+                }
+                Some(Ordering::Greater) | Some(Ordering::Equal) if rb.matches(v) => {
+                    assert!(ra.matches(v), "{} vs {}: {:?} {}", a, b, ordering, v);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn test_boundary_versions() {
+    let boundaries = |text: &str| {
+        semver::VersionReq::parse(text)
+            .unwrap()
+            .boundary_versions()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        boundaries("^1.2.3"),
+        [
+            "1.2.2",
+            "1.2.3-0",
+            "1.2.3",
+            "1.2.4",
+            "1.18446744073709551615.18446744073709551615",
+            "2.0.0-0",
+            "2.0.0",
+            "2.0.1",
+        ],
+    );
+    assert_eq!(
+        boundaries(">1.2.3-rc.1"),
+        ["1.2.2", "1.2.3-rc.1", "1.2.3-rc.1.0", "1.2.3"],
+    );
+    assert_eq!(boundaries("<0.0.0"), ["0.0.0-0", "0.0.0", "0.0.1"]);
+    assert_eq!(boundaries("=1.2.3"), ["1.2.2", "1.2.3-0", "1.2.3", "1.2.4"]);
+    assert_eq!(boundaries("*"), Vec::<String>::new());
+
+    // Each comparator contributes its bounds, even where another comparator
+    // makes them irrelevant.
+    assert_eq!(
+        boundaries(">=1.0.0 >=1.5.0 || 3.0.0 - 3.1.0"),
+        [
+            "0.18446744073709551615.18446744073709551615",
+            "1.0.0-0",
+            "1.0.0",
+            "1.0.1",
+            "1.4.18446744073709551615",
+            "1.5.0-0",
+            "1.5.0",
+            "1.5.1",
+            "2.18446744073709551615.18446744073709551615",
+            "3.0.0-0",
+            "3.0.0",
+            "3.0.1",
+            "3.0.18446744073709551615",
+            "3.1.0-0",
+            "3.1.0",
+            "3.1.1",
+        ],
+    );
+
+    // Matching changes between neighboring boundaries exactly at the bounds.
+    let req = semver::VersionReq::parse(">=1.2.3 <1.4").unwrap();
+    let matched = req
+        .boundary_versions()
+        .iter()
+        .map(|v| req.matches(v))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        matched,
+        [false, false, true, true, true, false, false, false]
+    );
+}
+
+#[test]
+fn test_into_predicate() {
+    fn predicate(text: &str) -> impl Fn(&semver::Version) -> bool {
+        // The req is a local that does not outlive this function.
+        let req = semver::VersionReq::parse(text).unwrap();
+        req.into_predicate()
+    }
+
+    let versions = ["0.9.0", "1.0.0", "1.2.0-rc.1", "1.5.3", "2.0.0"]
+        .iter()
+        .map(|v| semver::Version::parse(v).unwrap())
+        .collect::<Vec<_>>();
+
+    let matches = predicate("^1");
+    let filtered = versions.iter().filter(|v| matches(v)).collect::<Vec<_>>();
+    assert_eq!(filtered, [&versions[1], &versions[3]]);
+
+    // The closure can be used again, and the same as matching directly.
+    let req = semver::VersionReq::parse(">=1.2.0-rc.1").unwrap();
+    let matches = req.clone().into_predicate();
+    for v in &versions {
+        assert_eq!(matches(v), req.matches(v), "{}", v);
+    }
+
+    let mut retained = versions.clone();
+    retained.retain(predicate("<1.0.0 || >=2"));
+    assert_eq!(retained, [versions[0].clone(), versions[4].clone()]);
+}
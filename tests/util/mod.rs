@@ -37,3 +37,77 @@ pub(super) fn build_metadata(text: &str) -> BuildMetadata {
 pub(super) fn assert_to_string(value: impl Display, expected: &str) {
     assert_eq!(value.to_string(), expected);
 }
+
+// Deterministic pseudo-random numbers, so that failures in randomized tests
+// are reproducible.
+pub(super) struct Xorshift(u64);
+
+impl Xorshift {
+    pub(super) fn new() -> Self {
+        Xorshift::with_seed(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub(super) fn with_seed(seed: u64) -> Self {
+        Xorshift(seed)
+    }
+
+    pub(super) fn next_u64(&mut self) -> u64 {
+        // xorshift64
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub(super) fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[(self.next_u64() % items.len() as u64) as usize]
+    }
+}
+
+// Every comparator on small partial versions, with each operator, for
+// checking a property of reqs against matches.
+pub(super) fn small_comparators() -> Vec<String> {
+    let mut partials = Vec::new();
+    for major in 0..3 {
+        partials.push(format!("{}", major));
+        for minor in 0..3 {
+            partials.push(format!("{}.{}", major, minor));
+            for patch in 0..3 {
+                for pre in &["", "-0", "-alpha", "-rc.1"] {
+                    partials.push(format!("{}.{}.{}{}", major, minor, patch, pre));
+                }
+            }
+            partials.push(format!("{}.{}.*", major, minor));
+        }
+        partials.push(format!("{}.*", major));
+    }
+
+    let mut comparators = Vec::new();
+    for partial in &partials {
+        if partial.ends_with('*') {
+            comparators.push(partial.clone());
+            continue;
+        }
+        for op in &["", "=", ">", ">=", "<", "<=", "~", "^", "~>"] {
+            comparators.push(format!("{}{}", op, partial));
+        }
+    }
+    comparators
+}
+
+// Versions around and between the ones in small_comparators.
+pub(super) fn small_versions() -> Vec<Version> {
+    let mut versions = Vec::new();
+    for major in 0..4 {
+        for minor in 0..4 {
+            for patch in 0..4 {
+                for pre in &["", "-0", "-alpha", "-rc.1", "-rc.1.0", "-rc.2"] {
+                    versions.push(version(&format!("{}.{}.{}{}", major, minor, patch, pre)));
+                }
+            }
+        }
+    }
+    versions.push(version("1.2.3+build"));
+    versions.push(version("18446744073709551615.0.0"));
+    versions
+}